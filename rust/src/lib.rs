@@ -1,5 +1,106 @@
-use std::f64::consts::PI;
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::f64::consts::PI;
+
+/// The `f64` math methods that `core` leaves out, forwarded to `libm` for `no_std` builds.
+/// Each method means the same as the `std` method of the same name, so the calculators are
+/// written once against the usual method-call syntax.
+#[cfg(not(feature = "std"))]
+trait FloatMath {
+    fn sin(self) -> f64;
+    fn cos(self) -> f64;
+    fn tan(self) -> f64;
+    fn sin_cos(self) -> (f64, f64);
+    fn asin(self) -> f64;
+    fn atan(self) -> f64;
+    fn atan2(self, other: f64) -> f64;
+    fn sinh(self) -> f64;
+    fn cosh(self) -> f64;
+    fn asinh(self) -> f64;
+    fn atanh(self) -> f64;
+    fn sqrt(self) -> f64;
+    fn hypot(self, other: f64) -> f64;
+    fn powi(self, n: i32) -> f64;
+    fn floor(self) -> f64;
+    fn ceil(self) -> f64;
+    fn round(self) -> f64;
+    fn trunc(self) -> f64;
+    fn rem_euclid(self, rhs: f64) -> f64;
+}
+
+#[cfg(not(feature = "std"))]
+impl FloatMath for f64 {
+    fn sin(self) -> f64 {
+        libm::sin(self)
+    }
+    fn cos(self) -> f64 {
+        libm::cos(self)
+    }
+    fn tan(self) -> f64 {
+        libm::tan(self)
+    }
+    fn sin_cos(self) -> (f64, f64) {
+        libm::sincos(self)
+    }
+    fn asin(self) -> f64 {
+        libm::asin(self)
+    }
+    fn atan(self) -> f64 {
+        libm::atan(self)
+    }
+    fn atan2(self, other: f64) -> f64 {
+        libm::atan2(self, other)
+    }
+    fn sinh(self) -> f64 {
+        libm::sinh(self)
+    }
+    fn cosh(self) -> f64 {
+        libm::cosh(self)
+    }
+    fn asinh(self) -> f64 {
+        libm::asinh(self)
+    }
+    fn atanh(self) -> f64 {
+        libm::atanh(self)
+    }
+    fn sqrt(self) -> f64 {
+        libm::sqrt(self)
+    }
+    fn hypot(self, other: f64) -> f64 {
+        libm::hypot(self, other)
+    }
+    fn powi(self, n: i32) -> f64 {
+        libm::pow(self, n.into())
+    }
+    fn floor(self) -> f64 {
+        libm::floor(self)
+    }
+    fn ceil(self) -> f64 {
+        libm::ceil(self)
+    }
+    fn round(self) -> f64 {
+        libm::round(self)
+    }
+    fn trunc(self) -> f64 {
+        libm::trunc(self)
+    }
+    fn rem_euclid(self, rhs: f64) -> f64 {
+        let remainder = self % rhs;
+        if remainder < 0.0 {
+            remainder + rhs.abs()
+        } else {
+            remainder
+        }
+    }
+}
 use thiserror::Error;
+#[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::*;
 
 #[derive(Error, Debug, PartialEq)]
@@ -7,11 +108,76 @@ pub enum TwosComplementError {
     #[error("Invalid input: Enter only 0s and 1s.")]
     InvalidInput,
     #[error("Error parsing binary input: {0}")]
-    ParseError(#[from] std::num::ParseIntError),
+    ParseError(#[from] core::num::ParseIntError),
     #[error("Error: Size must be greater than 0.")]
     InvalidSize,
     #[error("Error: Number does not fit in the specified size.")]
     OverflowError,
+    #[error("Error: Radix {0} is not supported.")]
+    InvalidRadix(u32),
+}
+
+/// Discriminant of a [`TwosComplementError`] that can cross the wasm boundary.
+///
+/// JavaScript receives these as plain integers, so the frontend can tell an overflow from an
+/// invalid input without matching on the error message. `Ok` marks a successful conversion.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TwosComplementErrorKind {
+    Ok = 0,
+    InvalidInput = 1,
+    ParseError = 2,
+    InvalidSize = 3,
+    OverflowError = 4,
+    InvalidRadix = 5,
+}
+
+impl TwosComplementError {
+    /// Returns the kind of this error without its payload.
+    ///
+    /// ```
+    /// use rust::{TwosComplementError, TwosComplementErrorKind};
+    ///
+    /// assert_eq!(
+    ///     TwosComplementError::OverflowError.kind(),
+    ///     TwosComplementErrorKind::OverflowError
+    /// );
+    /// assert_eq!(TwosComplementError::InvalidRadix(1).kind() as u32, 5);
+    /// ```
+    pub fn kind(&self) -> TwosComplementErrorKind {
+        match self {
+            TwosComplementError::InvalidInput => TwosComplementErrorKind::InvalidInput,
+            TwosComplementError::ParseError(_) => TwosComplementErrorKind::ParseError,
+            TwosComplementError::InvalidSize => TwosComplementErrorKind::InvalidSize,
+            TwosComplementError::OverflowError => TwosComplementErrorKind::OverflowError,
+            TwosComplementError::InvalidRadix(_) => TwosComplementErrorKind::InvalidRadix,
+        }
+    }
+}
+
+/// Collapses a conversion result into its [`TwosComplementErrorKind`]
+#[cfg(feature = "wasm")]
+fn result_kind<T>(result: &Result<T, TwosComplementError>) -> TwosComplementErrorKind {
+    match result {
+        Ok(_) => TwosComplementErrorKind::Ok,
+        Err(e) => e.kind(),
+    }
+}
+
+/// Checks that the input is a non-empty string of 0s and 1s
+fn validate_binary(binary_input: &str) -> Result<(), TwosComplementError> {
+    if binary_input.is_empty() || !binary_input.chars().all(|c| c == '0' || c == '1') {
+        return Err(TwosComplementError::InvalidInput);
+    }
+    Ok(())
+}
+
+/// Flips every bit of a binary string
+fn invert_bits(binary_input: &str) -> String {
+    binary_input
+        .chars()
+        .map(|bit| if bit == '0' { '1' } else { '0' })
+        .collect()
 }
 
 /// Converts a two's complement binary string to its decimal value.
@@ -23,51 +189,296 @@ pub enum TwosComplementError {
 /// assert_eq!(calculate_twos_complement_rust("1101"), Ok(-3));
 /// assert_eq!(calculate_twos_complement_rust("0101"), Ok(5));
 /// assert_eq!(calculate_twos_complement_rust("111"), Ok(-1));
+/// assert_eq!(calculate_twos_complement_rust(&format!("1{}", "0".repeat(31))), Ok(i32::MIN));
 /// assert_eq!(
 ///     calculate_twos_complement_rust("invalid"),
 ///     Err(TwosComplementError::InvalidInput)
 /// );
 /// ```
 pub fn calculate_twos_complement_rust(binary_input: &str) -> Result<i32, TwosComplementError> {
-    if binary_input.is_empty() || !binary_input.chars().all(|c| c == '0' || c == '1') {
-        return Err(TwosComplementError::InvalidInput);
+    twos_complement_steps_rust(binary_input).map(|steps| steps.value)
+}
+
+/// Legacy wasm entry point that returns either the decimal value or the error message as a
+/// string. Prefer [`try_calculate_twos_complement`].
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn calculate_twos_complement(binary_input: &str) -> String {
+    match calculate_twos_complement_rust(binary_input) {
+        Ok(result) => result.to_string(),
+        Err(e) => e.to_string(),
+    }
+}
+
+/// Wasm entry point for [`calculate_twos_complement_rust`]. On failure the call throws the
+/// error message, so JS callers can tell errors from results with a `try`/`catch`.
+///
+/// ```
+/// use rust::try_calculate_twos_complement;
+///
+/// assert_eq!(try_calculate_twos_complement("1101"), Ok(-3));
+/// assert!(try_calculate_twos_complement("12").is_err());
+/// ```
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn try_calculate_twos_complement(binary_input: &str) -> Result<i32, String> {
+    calculate_twos_complement_rust(binary_input).map_err(|e| e.to_string())
+}
+
+/// The intermediate results of converting a two's complement binary string to decimal, for
+/// showing the conversion step by step.
+///
+/// Negative numbers are converted by inverting every bit, reading the result as an unsigned
+/// number and then negating it and subtracting one. Positive numbers are read directly, so
+/// `inverted` is `None` and `unsigned_value` equals `value`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TwosComplementSteps {
+    /// Whether the sign bit is set.
+    pub is_negative: bool,
+    /// The input with every bit inverted, only present for negative numbers.
+    pub inverted: Option<String>,
+    /// The unsigned value of the inverted bits, or of the input for positive numbers.
+    pub unsigned_value: u32,
+    /// The final decimal value.
+    pub value: i32,
+}
+
+impl TwosComplementSteps {
+    /// Serializes the steps as a JSON object, with `null` for a missing inversion step.
+    ///
+    /// ```
+    /// use rust::twos_complement_steps_rust;
+    ///
+    /// assert_eq!(
+    ///     twos_complement_steps_rust("1101").unwrap().to_json(),
+    ///     r#"{"is_negative":true,"inverted":"0010","unsigned_value":2,"value":-3}"#
+    /// );
+    /// assert_eq!(
+    ///     twos_complement_steps_rust("0101").unwrap().to_json(),
+    ///     r#"{"is_negative":false,"inverted":null,"unsigned_value":5,"value":5}"#
+    /// );
+    /// ```
+    pub fn to_json(&self) -> String {
+        // The inverted string only ever contains 0s and 1s, so it needs no escaping
+        let inverted = match &self.inverted {
+            Some(bits) => format!(r#""{}""#, bits),
+            None => "null".to_string(),
+        };
+        format!(
+            r#"{{"is_negative":{},"inverted":{},"unsigned_value":{},"value":{}}}"#,
+            self.is_negative, inverted, self.unsigned_value, self.value
+        )
     }
+}
+
+/// Converts a two's complement binary string to decimal like
+/// [`calculate_twos_complement_rust`], returning each step of the conversion.
+///
+/// Values outside the `i32` range return `OverflowError`, or `ParseError` if the unsigned
+/// value does not even fit in a `u32`.
+///
+/// # Examples
+///
+/// ```
+/// use rust::twos_complement_steps_rust;
+/// use rust::TwosComplementError;
+///
+/// let steps = twos_complement_steps_rust("1101").unwrap();
+/// assert!(steps.is_negative);
+/// assert_eq!(steps.inverted, Some("0010".to_string()));
+/// assert_eq!(steps.unsigned_value, 2);
+/// assert_eq!(steps.value, -3);
+///
+/// // Positive numbers skip the inversion step
+/// let steps = twos_complement_steps_rust("0101").unwrap();
+/// assert!(!steps.is_negative);
+/// assert_eq!(steps.inverted, None);
+/// assert_eq!(steps.unsigned_value, 5);
+/// assert_eq!(steps.value, 5);
+///
+/// // The 32-bit minimum, whose inverted bits are i32::MAX
+/// let steps = twos_complement_steps_rust(&format!("1{}", "0".repeat(31))).unwrap();
+/// assert_eq!(steps.inverted, Some(format!("0{}", "1".repeat(31))));
+/// assert_eq!(steps.unsigned_value, i32::MAX as u32);
+/// assert_eq!(steps.value, i32::MIN);
+///
+/// assert_eq!(
+///     twos_complement_steps_rust(&format!("1{}", "0".repeat(32))),
+///     Err(TwosComplementError::OverflowError)
+/// );
+/// assert_eq!(
+///     twos_complement_steps_rust("10a1"),
+///     Err(TwosComplementError::InvalidInput)
+/// );
+/// ```
+pub fn twos_complement_steps_rust(
+    binary_input: &str,
+) -> Result<TwosComplementSteps, TwosComplementError> {
+    validate_binary(binary_input)?;
 
     let is_negative = binary_input.starts_with('1');
-    let decimal_value: i32;
 
     if is_negative {
-        // Invert the bits and convert to decimal
-        let inverted_binary: String = binary_input
-            .chars()
-            .map(|bit| if bit == '0' { '1' } else { '0' })
-            .collect();
-        decimal_value = i32::from_str_radix(&inverted_binary, 2)
-            .map(|val| -(val + 1)) // If parsing is successful, negate and add 1
-            .map_err(TwosComplementError::ParseError)?;
+        // Invert the bits and convert to decimal, then negate and subtract 1
+        let inverted_binary = invert_bits(binary_input);
+        let unsigned_value =
+            u32::from_str_radix(&inverted_binary, 2).map_err(TwosComplementError::ParseError)?;
+        // Negate in i64, since the inverted bits of the minimum value are i32::MAX
+        let value = i32::try_from(-(i64::from(unsigned_value) + 1))
+            .map_err(|_| TwosComplementError::OverflowError)?;
+        Ok(TwosComplementSteps {
+            is_negative,
+            inverted: Some(inverted_binary),
+            unsigned_value,
+            value,
+        })
     } else {
         // Positive binary number, parse normally
-        decimal_value =
-            i32::from_str_radix(binary_input, 2).map_err(TwosComplementError::ParseError)?;
+        let unsigned_value =
+            u32::from_str_radix(binary_input, 2).map_err(TwosComplementError::ParseError)?;
+        let value =
+            i32::try_from(unsigned_value).map_err(|_| TwosComplementError::OverflowError)?;
+        Ok(TwosComplementSteps {
+            is_negative,
+            inverted: None,
+            unsigned_value,
+            value,
+        })
+    }
+}
+
+/// Wasm entry point for [`twos_complement_steps_rust`] that returns the steps as a JSON object
+/// (see [`TwosComplementSteps::to_json`]) and throws the error message on failure.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn twos_complement_steps(binary_input: &str) -> Result<String, String> {
+    twos_complement_steps_rust(binary_input)
+        .map(|steps| steps.to_json())
+        .map_err(|e| e.to_string())
+}
+
+/// Converts a two's complement number written in base 2, 8 or 16 to its decimal value.
+///
+/// The digits are read as an unsigned pattern of `size` bits whose top bit is the sign. A
+/// `0b`, `0o` or `0x` prefix matching the radix is accepted and ignored.
+///
+/// # Arguments
+///
+/// * `input` - The digits to decode, e.g. `"FF"` or `"0xFF"` for radix 16.
+/// * `radix` - The base of the digits: 2, 8 or 16.
+/// * `size` - The bit size of the number, between 1 and 64.
+///
+/// # Examples
+///
+/// ```
+/// use rust::calculate_twos_complement_radix_rust;
+/// use rust::TwosComplementError;
+///
+/// assert_eq!(calculate_twos_complement_radix_rust("FF", 16, 8), Ok(-1));
+/// assert_eq!(calculate_twos_complement_radix_rust("0xff", 16, 8), Ok(-1));
+/// assert_eq!(calculate_twos_complement_radix_rust("377", 8, 8), Ok(-1));
+/// assert_eq!(calculate_twos_complement_radix_rust("0o377", 8, 8), Ok(-1));
+/// assert_eq!(calculate_twos_complement_radix_rust("7F", 16, 8), Ok(127));
+/// assert_eq!(calculate_twos_complement_radix_rust("FF", 16, 16), Ok(255));
+/// assert_eq!(calculate_twos_complement_radix_rust("1101", 2, 4), Ok(-3));
+/// assert_eq!(
+///     calculate_twos_complement_radix_rust("8000000000000000", 16, 64),
+///     Ok(i64::MIN)
+/// );
+/// assert_eq!(
+///     calculate_twos_complement_radix_rust("1FF", 16, 8),
+///     Err(TwosComplementError::OverflowError)
+/// );
+/// assert_eq!(
+///     calculate_twos_complement_radix_rust("789", 8, 16),
+///     Err(TwosComplementError::InvalidInput)
+/// );
+/// assert_eq!(
+///     calculate_twos_complement_radix_rust("99", 10, 8),
+///     Err(TwosComplementError::InvalidRadix(10))
+/// );
+/// assert_eq!(
+///     calculate_twos_complement_radix_rust("FF", 16, 0),
+///     Err(TwosComplementError::InvalidSize)
+/// );
+/// ```
+pub fn calculate_twos_complement_radix_rust(
+    input: &str,
+    radix: u32,
+    size: usize,
+) -> Result<i64, TwosComplementError> {
+    let prefix = match radix {
+        2 => "0b",
+        8 => "0o",
+        16 => "0x",
+        _ => return Err(TwosComplementError::InvalidRadix(radix)),
+    };
+    if size == 0 || size > 64 {
+        return Err(TwosComplementError::InvalidSize);
+    }
+
+    let digits = match input.get(..2) {
+        Some(start) if start.eq_ignore_ascii_case(prefix) => &input[2..],
+        _ => input,
+    };
+    if digits.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) {
+        return Err(TwosComplementError::InvalidInput);
+    }
+
+    // Any value too large for a u128 is certainly too large for 64 bits
+    let value =
+        u128::from_str_radix(digits, radix).map_err(|_| TwosComplementError::OverflowError)?;
+    if value >> size != 0 {
+        return Err(TwosComplementError::OverflowError);
     }
 
-    Ok(decimal_value)
+    let is_negative = value >> (size - 1) & 1 == 1;
+    let decimal_value = if is_negative {
+        value as i128 - (1i128 << size)
+    } else {
+        value as i128
+    };
+
+    Ok(decimal_value as i64)
 }
 
+/// Legacy wasm entry point that returns either the decimal value or the error message as a
+/// string. Prefer [`try_calculate_twos_complement_radix`].
+#[cfg(feature = "wasm")]
 #[wasm_bindgen]
-pub fn calculate_twos_complement(binary_input: &str) -> String {
-    match calculate_twos_complement_rust(binary_input) {
+pub fn calculate_twos_complement_radix(input: &str, radix: u32, size: usize) -> String {
+    match calculate_twos_complement_radix_rust(input, radix, size) {
         Ok(result) => result.to_string(),
         Err(e) => e.to_string(),
     }
 }
 
+/// Wasm entry point for [`calculate_twos_complement_radix_rust`] that throws the error message
+/// on failure. The value is returned to JS as a `BigInt`.
+///
+/// ```
+/// use rust::try_calculate_twos_complement_radix;
+///
+/// assert_eq!(try_calculate_twos_complement_radix("FF", 16, 8), Ok(-1));
+/// assert!(try_calculate_twos_complement_radix("FG", 16, 8).is_err());
+/// ```
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn try_calculate_twos_complement_radix(
+    input: &str,
+    radix: u32,
+    size: usize,
+) -> Result<i64, String> {
+    calculate_twos_complement_radix_rust(input, radix, size).map_err(|e| e.to_string())
+}
+
 /// Converts a decimal number to its two's complement binary representation of a given bit size.
 ///
 /// # Arguments
 ///
 /// * `decimal` - The signed decimal number to convert.
-/// * `size` - The bit size of the binary representation (must be greater than 0).
+/// * `size` - The bit size of the binary representation (must be greater than 0). Sizes above
+///   64 bits are sign-extended.
 ///
 ///
 /// # Examples
@@ -82,227 +493,5917 @@ pub fn calculate_twos_complement(binary_input: &str) -> String {
 ///     decimal_to_twos_complement_rust(128, 8),
 ///     Err(TwosComplementError::OverflowError)
 /// );
+///
+/// // 32-bit boundaries
+/// assert_eq!(decimal_to_twos_complement_rust(-1, 32), Ok("1".repeat(32)));
+/// assert_eq!(
+///     decimal_to_twos_complement_rust(i32::MIN as i64, 32),
+///     Ok(format!("1{}", "0".repeat(31)))
+/// );
+/// assert_eq!(
+///     decimal_to_twos_complement_rust(i32::MAX as i64, 32),
+///     Ok(format!("0{}", "1".repeat(31)))
+/// );
+/// assert_eq!(
+///     decimal_to_twos_complement_rust(i32::MAX as i64 + 1, 32),
+///     Err(TwosComplementError::OverflowError)
+/// );
+///
+/// // 64-bit boundaries
+/// assert_eq!(
+///     decimal_to_twos_complement_rust(i64::MIN, 64),
+///     Ok(format!("1{}", "0".repeat(63)))
+/// );
+/// assert_eq!(
+///     decimal_to_twos_complement_rust(i64::MAX, 64),
+///     Ok(format!("0{}", "1".repeat(63)))
+/// );
+/// assert_eq!(decimal_to_twos_complement_rust(-2, 70), Ok(format!("{}0", "1".repeat(69))));
 /// assert_eq!(
 ///     decimal_to_twos_complement_rust(5, 0),
 ///     Err(TwosComplementError::InvalidSize)
 /// );
 /// ```
 pub fn decimal_to_twos_complement_rust(
-    decimal: i32,
+    decimal: i64,
     size: usize,
 ) -> Result<String, TwosComplementError> {
-    if size <= 0 {
+    if size == 0 {
         return Err(TwosComplementError::InvalidSize);
     }
 
-    let max_positive = (1 << (size - 1)) - 1;
-    let min_negative = -(1 << (size - 1));
-
-    if decimal > max_positive || decimal < min_negative {
-        return Err(TwosComplementError::OverflowError);
+    // Every i64 fits in 64 bits or more, so only narrower sizes need a range check
+    if let Some((min_negative, max_positive)) = twos_complement_range(size) {
+        if decimal > max_positive || decimal < min_negative {
+            return Err(TwosComplementError::OverflowError);
+        }
     }
 
-    if decimal >= 0 {
-        Ok(format!("{:0>width$b}", decimal, width = size))
-    } else {
-        let binary = format!("{:0>width$b}", (-decimal), width = size);
-        let ones_complement: String = binary
-            .chars()
-            .map(|bit| if bit == '0' { '1' } else { '0' })
-            .collect();
-        let result =
-            i32::from_str_radix(&ones_complement, 2).map_err(TwosComplementError::ParseError)? + 1;
-        Ok(format!("{:0>width$b}", result, width = size))
+    encode_twos_complement(decimal, size)
+}
+
+/// Returns the `(min, max)` values representable in `size` bits, or `None` if every `i64` fits.
+fn twos_complement_range(size: usize) -> Option<(i64, i64)> {
+    if size >= 64 {
+        return None;
     }
+    Some((-(1i64 << (size - 1)), (1i64 << (size - 1)) - 1))
 }
 
-#[wasm_bindgen]
-pub fn decimal_to_twos_complement(decimal: i32, size: usize) -> String {
-    match decimal_to_twos_complement_rust(decimal, size) {
-        Ok(result) => result,
-        Err(e) => e.to_string(),
+/// Writes `decimal` as `size` two's complement bits. The caller must have checked that
+/// `size` is non-zero and that `decimal` fits.
+fn encode_twos_complement(decimal: i64, size: usize) -> Result<String, TwosComplementError> {
+    // The bit pattern of an i64 is already its 64-bit two's complement representation
+    let binary = format!("{:064b}", decimal as u64);
+    if size <= 64 {
+        Ok(binary[64 - size..].to_string())
+    } else {
+        sign_extend_bits(&binary, size)
     }
 }
 
-/// Converts latitude and longitude on the WGS84 ellipsoid to Cartesian XYZ coordinates.
-///
-/// # Parameters
-/// - `latitude`: Latitude in degrees. Positive values indicate north of the equator, and negative values indicate south.
-/// - `longitude`: Longitude in degrees. Positive values indicate east of the Prime Meridian, and negative values indicate west.
-/// - `height`: Height above the WGS84 ellipsoid in meters. This is the elevation from the ellipsoid surface.
-///
-/// # Returns
-/// A tuple `(X, Y, Z)` representing the Cartesian coordinates in meters.
+/// Converts a decimal number to two's complement like [`decimal_to_twos_complement_rust`], but
+/// clamps values that do not fit to the nearest representable value instead of returning
+/// `OverflowError`.
 ///
-/// # WGS84 Ellipsoid Constants
-/// - `a`: Semi-major axis, 6378137.0 meters.
-/// - `f`: Flattening factor, 1 / 298.257222101.
-/// - `e2`: Square of eccentricity, calculated as `2 * f - f * f`.
+/// # Examples
 ///
-/// # Example
 /// ```
-/// use rust::lat_lon_to_xyz_rust;
-/// // Point on the equator at sea level
-/// let (x, y, z) = lat_lon_to_xyz_rust(0.0, 0.0, 0.0);
-/// assert!((x - 6378137.0).abs() < 1e-6);
-/// assert!(y.abs() < 1e-6);
-/// assert!(z.abs() < 1e-6);
+/// use rust::decimal_to_twos_complement_saturating_rust;
+/// use rust::TwosComplementError;
 ///
-/// // Point on the equator at the Prime Meridian with 1000 meters elevation
-/// let (x, y, z) = lat_lon_to_xyz_rust(0.0, 0.0, 1000.0);
-/// assert!((x - 6378137.0 - 1000.0).abs() < 1e-6);
-/// assert!(y.abs() < 1e-6);
-/// assert!(z.abs() < 1e-6);
+/// // Values in range are converted unchanged
+/// assert_eq!(
+///     decimal_to_twos_complement_saturating_rust(-5, 8),
+///     Ok("11111011".to_string())
+/// );
 ///
-/// // Point on the equator at 90 degrees East
-/// let (x, y, z) = lat_lon_to_xyz_rust(0.0, 90.0, 0.0);
-/// assert!(x.abs() < 1e-6);
-/// assert!((y - 6378137.0).abs() < 1e-6);
-/// assert!(z.abs() < 1e-6);
+/// // Out of range values saturate to 127 and -128
+/// assert_eq!(
+///     decimal_to_twos_complement_saturating_rust(200, 8),
+///     Ok("01111111".to_string())
+/// );
+/// assert_eq!(
+///     decimal_to_twos_complement_saturating_rust(-200, 8),
+///     Ok("10000000".to_string())
+/// );
+/// assert_eq!(
+///     decimal_to_twos_complement_saturating_rust(5, 1),
+///     Ok("0".to_string())
+/// );
+/// assert_eq!(
+///     decimal_to_twos_complement_saturating_rust(5, 0),
+///     Err(TwosComplementError::InvalidSize)
+/// );
 /// ```
-pub fn lat_lon_to_xyz_rust(latitude: f64, longitude: f64, height: f64) -> (f64, f64, f64) {
-    // WGS84 ellipsoid constants
-    let a = 6378137.0; // Semi-major axis in meters
-    let f = 1.0 / 298.257222101; // Flattening
-    let e2 = 2.0 * f - f * f; // Square of eccentricity
-
-    // Convert latitude and longitude from degrees to radians
-    let lat_rad = latitude * PI / 180.0;
-    let lon_rad = longitude * PI / 180.0;
-
-    // Calculate the prime vertical radius of curvature
-    let n = a / (1.0 - e2 * lat_rad.sin().powi(2)).sqrt();
-
-    // Calculate X, Y, and Z coordinates
-    let x = (n + height) * lat_rad.cos() * lon_rad.cos();
-    let y = (n + height) * lat_rad.cos() * lon_rad.sin();
-    let z = (n * (1.0 - e2) + height) * lat_rad.sin();
+pub fn decimal_to_twos_complement_saturating_rust(
+    decimal: i64,
+    size: usize,
+) -> Result<String, TwosComplementError> {
+    if size == 0 {
+        return Err(TwosComplementError::InvalidSize);
+    }
 
-    (x, y, z)
+    let clamped = match twos_complement_range(size) {
+        Some((min_negative, max_positive)) => decimal.clamp(min_negative, max_positive),
+        None => decimal,
+    };
+    encode_twos_complement(clamped, size)
 }
 
+#[cfg(feature = "wasm")]
 #[wasm_bindgen]
-pub fn lat_lon_to_xyz(latitude: f64, longitude: f64, height: f64) -> Vec<f64> {
-    let (x, y, z) = lat_lon_to_xyz_rust(latitude, longitude, height);
-    vec![x, y, z]
-}
-
-//
-// Find best UTM zone for a position
-//
-
-/// Custom error type for UTM zone calculation using `thiserror`
-#[derive(Debug, Error)]
-pub enum UTMZoneError {
-    #[error("Invalid longitude: {0}. Longitude must be between -180 and 180 degrees.")]
-    InvalidLongitude(f64),
-    #[error("Invalid latitude: {0}. Latitude must be between -90 and 90 degrees.")]
-    InvalidLatitude(f64),
-    #[error("{0}")]
-    CalculationError(String),
+pub fn decimal_to_twos_complement_saturating(decimal: i32, size: usize) -> Result<String, String> {
+    decimal_to_twos_complement_saturating_rust(decimal.into(), size).map_err(|e| e.to_string())
 }
 
-/// Returns the MGRS latitude band letter for a given latitude
-fn get_mgrs_latitude_band(latitude: f64) -> Result<char, UTMZoneError> {
-    if latitude < -80.0 || latitude >= 84.0 {
-        return Err(UTMZoneError::InvalidLatitude(latitude));
+/// Legacy wasm entry point that returns either the binary string or the error message.
+/// Prefer [`try_decimal_to_twos_complement`].
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn decimal_to_twos_complement(decimal: i32, size: usize) -> String {
+    match decimal_to_twos_complement_rust(decimal.into(), size) {
+        Ok(result) => result,
+        Err(e) => e.to_string(),
     }
-
-    let bands: Vec<char> = ('C'..='X').filter(|&c| c != 'I' && c != 'O').collect();
-
-    let index = ((latitude + 80.0) / 8.0).floor() as usize;
-    Ok(bands[index])
 }
 
-/// Calculates the UTM zone number and MGRS latitude band for a given latitude and longitude.
+/// Wasm entry point for [`decimal_to_twos_complement_rust`] that throws the error message on
+/// failure.
 ///
-/// The function handles special UTM zone cases, such as areas in Norway and Svalbard, where
-/// UTM zones deviate from the regular 6-degree longitudinal spacing. It also incorporates
-/// the MGRS latitude bands, which range from 'C' to 'X' (excluding 'I' and 'O').
+/// ```
+/// use rust::try_decimal_to_twos_complement;
 ///
-/// # Returns
-/// - A `Result` containing the UTM zone number and MGRS latitude band, or an error if the inputs
-///   are outside the valid latitude or longitude range.
+/// assert_eq!(try_decimal_to_twos_complement(-5, 8), Ok("11111011".to_string()));
+/// assert_eq!(
+///     try_decimal_to_twos_complement(128, 8),
+///     Err("Error: Number does not fit in the specified size.".to_string())
+/// );
+/// ```
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn try_decimal_to_twos_complement(decimal: i32, size: usize) -> Result<String, String> {
+    decimal_to_twos_complement_rust(decimal.into(), size).map_err(|e| e.to_string())
+}
+
+/// Converts a decimal number to two's complement like [`decimal_to_twos_complement_rust`], with
+/// a space inserted every `group` bits counting from the right.
+///
+/// A `group` of 0 disables spacing and gives the same output as the ungrouped function.
+///
+/// # Examples
+///
+/// ```
+/// use rust::decimal_to_twos_complement_grouped_rust;
+/// use rust::TwosComplementError;
+///
+/// assert_eq!(
+///     decimal_to_twos_complement_grouped_rust(-5, 8, 4),
+///     Ok("1111 1011".to_string())
+/// );
+/// assert_eq!(
+///     decimal_to_twos_complement_grouped_rust(5, 12, 4),
+///     Ok("0000 0000 0101".to_string())
+/// );
+/// assert_eq!(
+///     decimal_to_twos_complement_grouped_rust(-5, 10, 4),
+///     Ok("11 1111 1011".to_string())
+/// );
+/// assert_eq!(
+///     decimal_to_twos_complement_grouped_rust(-5, 8, 0),
+///     Ok("11111011".to_string())
+/// );
+/// assert_eq!(
+///     decimal_to_twos_complement_grouped_rust(128, 8, 4),
+///     Err(TwosComplementError::OverflowError)
+/// );
+/// ```
+pub fn decimal_to_twos_complement_grouped_rust(
+    decimal: i64,
+    size: usize,
+    group: usize,
+) -> Result<String, TwosComplementError> {
+    let binary = decimal_to_twos_complement_rust(decimal, size)?;
+    Ok(group_digits(&binary, group))
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn decimal_to_twos_complement_grouped(
+    decimal: i32,
+    size: usize,
+    group: usize,
+) -> Result<String, String> {
+    decimal_to_twos_complement_grouped_rust(decimal.into(), size, group).map_err(|e| e.to_string())
+}
+
+/// Byte order of a multi-byte binary string.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Endianness {
+    /// Most significant byte first, the order used by every other function in this crate
+    Big,
+    /// Least significant byte first
+    Little,
+}
+
+/// Converts a decimal number to two's complement like [`decimal_to_twos_complement_rust`] and
+/// returns its bytes in the requested order.
+///
+/// Bits within each byte stay most significant first. Little-endian output needs a whole
+/// number of bytes, so it fails with `InvalidSize` when `size` is not a multiple of 8.
+///
+/// # Examples
+///
+/// ```
+/// use rust::{decimal_to_twos_complement_endian_rust, Endianness};
+/// use rust::TwosComplementError;
+///
+/// assert_eq!(
+///     decimal_to_twos_complement_endian_rust(1, 16, Endianness::Big),
+///     Ok("0000000000000001".to_string())
+/// );
+/// assert_eq!(
+///     decimal_to_twos_complement_endian_rust(1, 16, Endianness::Little),
+///     Ok("0000000100000000".to_string())
+/// );
+/// assert_eq!(
+///     decimal_to_twos_complement_endian_rust(-2, 24, Endianness::Little),
+///     Ok("111111101111111111111111".to_string())
+/// );
+/// assert_eq!(
+///     decimal_to_twos_complement_endian_rust(1, 12, Endianness::Big),
+///     Ok("000000000001".to_string())
+/// );
+/// assert_eq!(
+///     decimal_to_twos_complement_endian_rust(1, 12, Endianness::Little),
+///     Err(TwosComplementError::InvalidSize)
+/// );
+/// ```
+pub fn decimal_to_twos_complement_endian_rust(
+    decimal: i64,
+    size: usize,
+    endianness: Endianness,
+) -> Result<String, TwosComplementError> {
+    let binary = decimal_to_twos_complement_rust(decimal, size)?;
+    match endianness {
+        Endianness::Big => Ok(binary),
+        Endianness::Little => {
+            if !size.is_multiple_of(8) {
+                return Err(TwosComplementError::InvalidSize);
+            }
+            Ok((0..size / 8)
+                .rev()
+                .map(|byte| &binary[byte * 8..byte * 8 + 8])
+                .collect())
+        }
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn decimal_to_twos_complement_endian(
+    decimal: i32,
+    size: usize,
+    endianness: Endianness,
+) -> Result<String, String> {
+    decimal_to_twos_complement_endian_rust(decimal.into(), size, endianness)
+        .map_err(|e| e.to_string())
+}
+
+/// Wasm entry point that reports how [`calculate_twos_complement_rust`] would fail, or
+/// [`TwosComplementErrorKind::Ok`] if the input converts.
+///
+/// ```
+/// use rust::{calculate_twos_complement_error_kind, TwosComplementErrorKind};
+///
+/// assert_eq!(calculate_twos_complement_error_kind("1011"), TwosComplementErrorKind::Ok);
+/// assert_eq!(
+///     calculate_twos_complement_error_kind("10a1"),
+///     TwosComplementErrorKind::InvalidInput
+/// );
+/// ```
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn calculate_twos_complement_error_kind(binary_input: &str) -> TwosComplementErrorKind {
+    result_kind(&calculate_twos_complement_rust(binary_input))
+}
+
+/// Wasm entry point that reports how [`decimal_to_twos_complement_rust`] would fail, or
+/// [`TwosComplementErrorKind::Ok`] if the number fits.
+///
+/// ```
+/// use rust::{decimal_to_twos_complement_error_kind, TwosComplementErrorKind};
+///
+/// assert_eq!(decimal_to_twos_complement_error_kind(-5, 8), TwosComplementErrorKind::Ok);
+/// assert_eq!(
+///     decimal_to_twos_complement_error_kind(128, 8),
+///     TwosComplementErrorKind::OverflowError
+/// );
+/// assert_eq!(
+///     decimal_to_twos_complement_error_kind(5, 0),
+///     TwosComplementErrorKind::InvalidSize
+/// );
+/// ```
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn decimal_to_twos_complement_error_kind(decimal: i32, size: usize) -> TwosComplementErrorKind {
+    result_kind(&decimal_to_twos_complement_rust(decimal.into(), size))
+}
+
+/// Converts many two's complement binary strings to their decimal values in one call.
+///
+/// Each element is converted independently with [`calculate_twos_complement_rust`], so one
+/// invalid input does not affect the others.
+///
+/// # Examples
+///
+/// ```
+/// use rust::calculate_twos_complement_batch_rust;
+/// use rust::TwosComplementError;
+///
+/// assert_eq!(
+///     calculate_twos_complement_batch_rust(&["1101", "bad", "0101", ""]),
+///     vec![
+///         Ok(-3),
+///         Err(TwosComplementError::InvalidInput),
+///         Ok(5),
+///         Err(TwosComplementError::InvalidInput),
+///     ]
+/// );
+/// ```
+pub fn calculate_twos_complement_batch_rust<S: AsRef<str>>(
+    inputs: &[S],
+) -> Vec<Result<i32, TwosComplementError>> {
+    inputs
+        .iter()
+        .map(|input| calculate_twos_complement_rust(input.as_ref()))
+        .collect()
+}
+
+/// Batch version of [`calculate_twos_complement`] that converts a whole array in a single call
+/// across the wasm boundary. Errors are kept inline as their messages.
+///
+/// ```
+/// use rust::calculate_twos_complement_batch;
+///
+/// let outputs = calculate_twos_complement_batch(vec!["111".to_string(), "12".to_string()]);
+/// assert_eq!(outputs, vec!["-1", "Invalid input: Enter only 0s and 1s."]);
+/// ```
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn calculate_twos_complement_batch(inputs: Vec<String>) -> Vec<String> {
+    calculate_twos_complement_batch_rust(&inputs)
+        .into_iter()
+        .map(|result| match result {
+            Ok(result) => result.to_string(),
+            Err(e) => e.to_string(),
+        })
+        .collect()
+}
+
+/// Converts many decimal numbers to two's complement binary strings of the same bit size in
+/// one call.
+///
+/// Each element is converted independently with [`decimal_to_twos_complement_rust`].
+///
+/// # Examples
+///
+/// ```
+/// use rust::decimal_to_twos_complement_batch_rust;
+/// use rust::TwosComplementError;
+///
+/// assert_eq!(
+///     decimal_to_twos_complement_batch_rust(&[5, -5, 128, -128], 8),
+///     vec![
+///         Ok("00000101".to_string()),
+///         Ok("11111011".to_string()),
+///         Err(TwosComplementError::OverflowError),
+///         Ok("10000000".to_string()),
+///     ]
+/// );
+/// ```
+pub fn decimal_to_twos_complement_batch_rust(
+    decimals: &[i64],
+    size: usize,
+) -> Vec<Result<String, TwosComplementError>> {
+    decimals
+        .iter()
+        .map(|&decimal| decimal_to_twos_complement_rust(decimal, size))
+        .collect()
+}
+
+/// Batch version of [`decimal_to_twos_complement`] that converts a whole array in a single
+/// call across the wasm boundary. Errors are kept inline as their messages.
+///
+/// ```
+/// use rust::decimal_to_twos_complement_batch;
+///
+/// let outputs = decimal_to_twos_complement_batch(vec![-1, 8], 4);
+/// assert_eq!(outputs, vec!["1111", "Error: Number does not fit in the specified size."]);
+/// ```
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn decimal_to_twos_complement_batch(decimals: Vec<i32>, size: usize) -> Vec<String> {
+    let decimals: Vec<i64> = decimals.into_iter().map(i64::from).collect();
+    decimal_to_twos_complement_batch_rust(&decimals, size)
+        .into_iter()
+        .map(|result| match result {
+            Ok(result) => result,
+            Err(e) => e.to_string(),
+        })
+        .collect()
+}
+
+/// Inserts a space every `group` digits counting from the right. A `group` of 0 disables
+/// grouping.
+fn group_digits(digits: &str, group: usize) -> String {
+    if group == 0 {
+        return digits.to_string();
+    }
+
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / group);
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(group) {
+            grouped.push(' ');
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+/// Converts a decimal number to its representation in an arbitrary radix.
+///
+/// The magnitude is written in the chosen base with uppercase letters for digits above 9, and
+/// negative numbers get a leading minus sign. This is a plain signed representation, not a
+/// two's complement one.
+///
+/// # Arguments
+///
+/// * `decimal` - The signed decimal number to convert.
+/// * `radix` - The base of the output, between 2 and 36.
+/// * `group` - Insert a space every `group` digits from the right (0 disables grouping).
+///
+/// # Examples
+///
+/// ```
+/// use rust::decimal_to_radix_rust;
+/// use rust::TwosComplementError;
+///
+/// assert_eq!(decimal_to_radix_rust(255, 16, 2), Ok("FF".to_string()));
+/// assert_eq!(decimal_to_radix_rust(-10, 2, 4), Ok("-1010".to_string()));
+/// assert_eq!(decimal_to_radix_rust(255, 2, 4), Ok("1111 1111".to_string()));
+/// assert_eq!(decimal_to_radix_rust(4095, 8, 0), Ok("7777".to_string()));
+/// assert_eq!(decimal_to_radix_rust(0, 16, 2), Ok("0".to_string()));
+/// assert_eq!(decimal_to_radix_rust(35, 36, 0), Ok("Z".to_string()));
+/// assert_eq!(
+///     decimal_to_radix_rust(i64::MIN, 16, 4),
+///     Ok("-8000 0000 0000 0000".to_string())
+/// );
+/// assert_eq!(
+///     decimal_to_radix_rust(10, 1, 0),
+///     Err(TwosComplementError::InvalidRadix(1))
+/// );
+/// assert_eq!(
+///     decimal_to_radix_rust(10, 37, 0),
+///     Err(TwosComplementError::InvalidRadix(37))
+/// );
+/// ```
+pub fn decimal_to_radix_rust(
+    decimal: i64,
+    radix: u32,
+    group: usize,
+) -> Result<String, TwosComplementError> {
+    if !(2..=36).contains(&radix) {
+        return Err(TwosComplementError::InvalidRadix(radix));
+    }
+
+    let mut magnitude = decimal.unsigned_abs();
+    let mut digits = Vec::new();
+    loop {
+        let digit = (magnitude % radix as u64) as u32;
+        // The radix was validated above, so every digit is representable
+        digits.push(
+            core::char::from_digit(digit, radix)
+                .unwrap()
+                .to_ascii_uppercase(),
+        );
+        magnitude /= radix as u64;
+        if magnitude == 0 {
+            break;
+        }
+    }
+    let digits: String = digits.iter().rev().collect();
+
+    let sign = if decimal < 0 { "-" } else { "" };
+    Ok(format!("{}{}", sign, group_digits(&digits, group)))
+}
+
+/// Legacy wasm entry point that returns either the digits or the error message.
+/// Prefer [`try_decimal_to_radix`].
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn decimal_to_radix(decimal: i32, radix: u32, group: usize) -> String {
+    match decimal_to_radix_rust(decimal.into(), radix, group) {
+        Ok(result) => result,
+        Err(e) => e.to_string(),
+    }
+}
+
+/// Wasm entry point for [`decimal_to_radix_rust`] that throws the error message on failure.
+///
+/// ```
+/// use rust::try_decimal_to_radix;
+///
+/// assert_eq!(try_decimal_to_radix(255, 16, 0), Ok("FF".to_string()));
+/// assert!(try_decimal_to_radix(255, 40, 0).is_err());
+/// ```
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn try_decimal_to_radix(decimal: i32, radix: u32, group: usize) -> Result<String, String> {
+    decimal_to_radix_rust(decimal.into(), radix, group).map_err(|e| e.to_string())
+}
+
+/// Sign-extends a two's complement binary string to `size` bits
+fn sign_extend_bits(binary_input: &str, size: usize) -> Result<String, TwosComplementError> {
+    if size == 0 {
+        return Err(TwosComplementError::InvalidSize);
+    }
+    validate_binary(binary_input)?;
+    if binary_input.len() > size {
+        return Err(TwosComplementError::OverflowError);
+    }
+
+    let sign = &binary_input[..1];
+    Ok(sign.repeat(size - binary_input.len()) + binary_input)
+}
+
+/// Sign-extends a two's complement binary string to a wider bit size.
+///
+/// The sign bit is replicated into the new high bits, so the represented value is unchanged.
+///
+/// # Arguments
+///
+/// * `binary_input` - The two's complement bits to extend.
+/// * `from_size` - The bit size of the input, which must match its length.
+/// * `to_size` - The target bit size, which must be at least `from_size`.
+///
+/// # Examples
+///
+/// ```
+/// use rust::sign_extend_rust;
+/// use rust::TwosComplementError;
+///
+/// assert_eq!(sign_extend_rust("1101", 4, 8), Ok("11111101".to_string()));
+/// assert_eq!(sign_extend_rust("0101", 4, 8), Ok("00000101".to_string()));
+/// assert_eq!(sign_extend_rust("1101", 4, 4), Ok("1101".to_string()));
+/// assert_eq!(
+///     sign_extend_rust("1101", 3, 8),
+///     Err(TwosComplementError::InvalidSize)
+/// );
+/// assert_eq!(
+///     sign_extend_rust("1101", 4, 2),
+///     Err(TwosComplementError::InvalidSize)
+/// );
+/// assert_eq!(
+///     sign_extend_rust("11a1", 4, 8),
+///     Err(TwosComplementError::InvalidInput)
+/// );
+/// ```
+pub fn sign_extend_rust(
+    binary_input: &str,
+    from_size: usize,
+    to_size: usize,
+) -> Result<String, TwosComplementError> {
+    validate_binary(binary_input)?;
+    if binary_input.len() != from_size || to_size < from_size {
+        return Err(TwosComplementError::InvalidSize);
+    }
+
+    sign_extend_bits(binary_input, to_size)
+}
+
+/// Truncates a two's complement binary string to its low `to_size` bits.
+///
+/// # Returns
+/// - A `Result` containing the truncated bits and a flag that is `true` when truncation changed
+///   the represented value, i.e. the dropped bits were not all copies of the new sign bit.
+///
+/// # Examples
+///
+/// ```
+/// use rust::truncate_rust;
+/// use rust::TwosComplementError;
+///
+/// // -3 survives narrowing from 8 to 4 bits
+/// assert_eq!(truncate_rust("11111101", 4), Ok(("1101".to_string(), false)));
+///
+/// // 13 does not fit in 4 bits and becomes -3
+/// assert_eq!(truncate_rust("00001101", 4), Ok(("1101".to_string(), true)));
+///
+/// assert_eq!(
+///     truncate_rust("1101", 8),
+///     Err(TwosComplementError::InvalidSize)
+/// );
+/// assert_eq!(
+///     truncate_rust("1101", 0),
+///     Err(TwosComplementError::InvalidSize)
+/// );
+/// ```
+pub fn truncate_rust(
+    binary_input: &str,
+    to_size: usize,
+) -> Result<(String, bool), TwosComplementError> {
+    validate_binary(binary_input)?;
+    if to_size == 0 || to_size > binary_input.len() {
+        return Err(TwosComplementError::InvalidSize);
+    }
+
+    let split = binary_input.len() - to_size;
+    let truncated = &binary_input[split..];
+    let sign = truncated.as_bytes()[0];
+    let value_changed = binary_input.as_bytes()[..split]
+        .iter()
+        .any(|&bit| bit != sign);
+
+    Ok((truncated.to_string(), value_changed))
+}
+
+/// Adds two binary strings of equal length with a ripple carry, dropping the final carry out
+fn add_bits(a: &str, b: &str, carry_in: bool) -> String {
+    let mut carry = carry_in;
+    let mut result: Vec<char> = a
+        .chars()
+        .rev()
+        .zip(b.chars().rev())
+        .map(|(bit_a, bit_b)| {
+            let ones = [bit_a == '1', bit_b == '1', carry]
+                .iter()
+                .filter(|&&bit| bit)
+                .count();
+            carry = ones >= 2;
+            if ones % 2 == 1 {
+                '1'
+            } else {
+                '0'
+            }
+        })
+        .collect();
+    result.reverse();
+    result.into_iter().collect()
+}
+
+/// Adds two two's complement binary numbers in a fixed bit size.
+///
+/// Operands shorter than `size` are sign-extended, so `"1101"` is read as -3 regardless of the
+/// size. The sum wraps around like a hardware adder and signed overflow is reported
+/// separately.
+///
+/// # Returns
+/// - A `Result` containing the `size`-bit sum and a flag that is `true` when signed overflow
+///   occurred, i.e. both operands have the same sign and the result has the other one.
+///
+/// # Examples
+///
+/// ```
+/// use rust::twos_complement_add_rust;
+/// use rust::TwosComplementError;
+///
+/// // 127 + 1 overflows in 8 bits
+/// assert_eq!(
+///     twos_complement_add_rust("01111111", "00000001", 8),
+///     Ok(("10000000".to_string(), true))
+/// );
+///
+/// // -1 + 1 produces a carry out but no signed overflow
+/// assert_eq!(
+///     twos_complement_add_rust("11111111", "00000001", 8),
+///     Ok(("00000000".to_string(), false))
+/// );
+///
+/// // Short operands are sign-extended: -3 + 5 = 2
+/// assert_eq!(
+///     twos_complement_add_rust("1101", "0101", 8),
+///     Ok(("00000010".to_string(), false))
+/// );
+///
+/// assert_eq!(
+///     twos_complement_add_rust("101010101", "1", 8),
+///     Err(TwosComplementError::OverflowError)
+/// );
+/// assert_eq!(
+///     twos_complement_add_rust("0102", "1", 8),
+///     Err(TwosComplementError::InvalidInput)
+/// );
+/// ```
+pub fn twos_complement_add_rust(
+    a: &str,
+    b: &str,
+    size: usize,
+) -> Result<(String, bool), TwosComplementError> {
+    let a = sign_extend_bits(a, size)?;
+    let b = sign_extend_bits(b, size)?;
+
+    let result = add_bits(&a, &b, false);
+    let overflow =
+        a.starts_with('1') == b.starts_with('1') && result.starts_with('1') != a.starts_with('1');
+
+    Ok((result, overflow))
+}
+
+/// Subtracts two two's complement binary numbers in a fixed bit size.
+///
+/// Computes `a - b` as `a + !b + 1`, with the same operand handling as
+/// [`twos_complement_add_rust`].
+///
+/// # Returns
+/// - A `Result` containing the `size`-bit difference and a flag that is `true` when signed
+///   overflow occurred, i.e. the operands have different signs and the result does not have
+///   the sign of `a`.
+///
+/// # Examples
+///
+/// ```
+/// use rust::twos_complement_subtract_rust;
+///
+/// // 5 - 3 = 2
+/// assert_eq!(
+///     twos_complement_subtract_rust("0101", "0011", 4),
+///     Ok(("0010".to_string(), false))
+/// );
+///
+/// // -128 - 1 overflows in 8 bits
+/// assert_eq!(
+///     twos_complement_subtract_rust("10000000", "00000001", 8),
+///     Ok(("01111111".to_string(), true))
+/// );
+///
+/// // 0 - (-8) overflows in 4 bits
+/// assert_eq!(
+///     twos_complement_subtract_rust("0000", "1000", 4),
+///     Ok(("1000".to_string(), true))
+/// );
+/// ```
+pub fn twos_complement_subtract_rust(
+    a: &str,
+    b: &str,
+    size: usize,
+) -> Result<(String, bool), TwosComplementError> {
+    let a = sign_extend_bits(a, size)?;
+    let b = sign_extend_bits(b, size)?;
+
+    let result = add_bits(&a, &invert_bits(&b), true);
+    let overflow =
+        a.starts_with('1') != b.starts_with('1') && result.starts_with('1') != a.starts_with('1');
+
+    Ok((result, overflow))
+}
+
+/// Checks that both operands have the same length, sign-extends them to `size` bits and
+/// combines them bit by bit
+fn bitwise_operation(
+    a: &str,
+    b: &str,
+    size: usize,
+    operation: fn(bool, bool) -> bool,
+) -> Result<String, TwosComplementError> {
+    validate_binary(a)?;
+    validate_binary(b)?;
+    if a.len() != b.len() {
+        return Err(TwosComplementError::InvalidSize);
+    }
+
+    let a = sign_extend_bits(a, size)?;
+    let b = sign_extend_bits(b, size)?;
+
+    Ok(a.chars()
+        .zip(b.chars())
+        .map(|(bit_a, bit_b)| {
+            if operation(bit_a == '1', bit_b == '1') {
+                '1'
+            } else {
+                '0'
+            }
+        })
+        .collect())
+}
+
+/// Bitwise AND of two two's complement binary numbers in a fixed bit size.
+///
+/// Both operands must have the same length, otherwise `InvalidSize` is returned. Operands
+/// shorter than `size` are then sign-extended, as in [`twos_complement_add_rust`], and operands
+/// longer than `size` are rejected.
+///
+/// # Examples
+///
+/// ```
+/// use rust::twos_complement_and_rust;
+/// use rust::TwosComplementError;
+///
+/// assert_eq!(twos_complement_and_rust("1100", "1010", 4), Ok("1000".to_string()));
+/// assert_eq!(twos_complement_and_rust("1100", "0110", 8), Ok("00000100".to_string()));
+/// assert_eq!(
+///     twos_complement_and_rust("11001", "10101", 4),
+///     Err(TwosComplementError::OverflowError)
+/// );
+///
+/// // Operands of different lengths are rejected rather than sign-extended
+/// assert_eq!(
+///     twos_complement_and_rust("1100", "110", 8),
+///     Err(TwosComplementError::InvalidSize)
+/// );
+/// ```
+pub fn twos_complement_and_rust(
+    a: &str,
+    b: &str,
+    size: usize,
+) -> Result<String, TwosComplementError> {
+    bitwise_operation(a, b, size, |x, y| x & y)
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn twos_complement_and(a: &str, b: &str, size: usize) -> Result<String, String> {
+    twos_complement_and_rust(a, b, size).map_err(|e| e.to_string())
+}
+
+/// Bitwise OR of two two's complement binary numbers in a fixed bit size.
+///
+/// Operands are handled as in [`twos_complement_and_rust`].
+///
+/// # Examples
+///
+/// ```
+/// use rust::twos_complement_or_rust;
+/// use rust::TwosComplementError;
+///
+/// assert_eq!(twos_complement_or_rust("1100", "1010", 4), Ok("1110".to_string()));
+/// assert_eq!(
+///     twos_complement_or_rust("1100", "10a0", 4),
+///     Err(TwosComplementError::InvalidInput)
+/// );
+/// ```
+pub fn twos_complement_or_rust(
+    a: &str,
+    b: &str,
+    size: usize,
+) -> Result<String, TwosComplementError> {
+    bitwise_operation(a, b, size, |x, y| x | y)
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn twos_complement_or(a: &str, b: &str, size: usize) -> Result<String, String> {
+    twos_complement_or_rust(a, b, size).map_err(|e| e.to_string())
+}
+
+/// Bitwise XOR of two two's complement binary numbers in a fixed bit size.
+///
+/// Operands are handled as in [`twos_complement_and_rust`].
+///
+/// # Examples
+///
+/// ```
+/// use rust::twos_complement_xor_rust;
+///
+/// use rust::TwosComplementError;
+///
+/// assert_eq!(twos_complement_xor_rust("1100", "1010", 4), Ok("0110".to_string()));
+/// assert_eq!(twos_complement_xor_rust("1", "0", 4), Ok("1111".to_string()));
+/// assert_eq!(
+///     twos_complement_xor_rust("1", "00", 4),
+///     Err(TwosComplementError::InvalidSize)
+/// );
+/// ```
+pub fn twos_complement_xor_rust(
+    a: &str,
+    b: &str,
+    size: usize,
+) -> Result<String, TwosComplementError> {
+    bitwise_operation(a, b, size, |x, y| x ^ y)
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn twos_complement_xor(a: &str, b: &str, size: usize) -> Result<String, String> {
+    twos_complement_xor_rust(a, b, size).map_err(|e| e.to_string())
+}
+
+/// Bitwise NOT of a two's complement binary number in a fixed bit size.
+///
+/// The operand is sign-extended to `size` bits before every bit is flipped.
+///
+/// # Examples
+///
+/// ```
+/// use rust::twos_complement_not_rust;
+/// use rust::TwosComplementError;
+///
+/// assert_eq!(twos_complement_not_rust("1100", 4), Ok("0011".to_string()));
+/// assert_eq!(twos_complement_not_rust("0101", 8), Ok("11111010".to_string()));
+/// assert_eq!(
+///     twos_complement_not_rust("1100", 0),
+///     Err(TwosComplementError::InvalidSize)
+/// );
+/// ```
+pub fn twos_complement_not_rust(
+    binary_input: &str,
+    size: usize,
+) -> Result<String, TwosComplementError> {
+    Ok(invert_bits(&sign_extend_bits(binary_input, size)?))
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn twos_complement_not(binary_input: &str, size: usize) -> Result<String, String> {
+    twos_complement_not_rust(binary_input, size).map_err(|e| e.to_string())
+}
+
+/// Shifts a two's complement binary number left, filling with zeros.
+///
+/// The operand is sign-extended to `size` bits first. Bits shifted past the left end are
+/// dropped, so shifting by `size` or more yields all zeros.
+///
+/// # Examples
+///
+/// ```
+/// use rust::shift_left_rust;
+/// use rust::TwosComplementError;
+///
+/// assert_eq!(shift_left_rust("0011", 4, 1), Ok("0110".to_string()));
+/// assert_eq!(shift_left_rust("1011", 4, 2), Ok("1100".to_string()));
+/// assert_eq!(shift_left_rust("1011", 4, 4), Ok("0000".to_string()));
+/// assert_eq!(shift_left_rust("1011", 4, 0), Ok("1011".to_string()));
+/// assert_eq!(
+///     shift_left_rust("10111", 4, 1),
+///     Err(TwosComplementError::OverflowError)
+/// );
+/// ```
+pub fn shift_left_rust(
+    binary_input: &str,
+    size: usize,
+    amount: usize,
+) -> Result<String, TwosComplementError> {
+    let bits = sign_extend_bits(binary_input, size)?;
+    let amount = amount.min(size);
+    Ok(bits[amount..].to_string() + &"0".repeat(amount))
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn shift_left(binary_input: &str, size: usize, amount: usize) -> Result<String, String> {
+    shift_left_rust(binary_input, size, amount).map_err(|e| e.to_string())
+}
+
+/// Shifts a two's complement binary number right, filling with zeros.
+///
+/// The operand is sign-extended to `size` bits first. Shifting by `size` or more yields all
+/// zeros.
+///
+/// # Examples
+///
+/// ```
+/// use rust::logical_shift_right_rust;
+///
+/// assert_eq!(logical_shift_right_rust("1000", 4, 1), Ok("0100".to_string()));
+/// assert_eq!(logical_shift_right_rust("1011", 4, 2), Ok("0010".to_string()));
+/// assert_eq!(logical_shift_right_rust("1011", 4, 9), Ok("0000".to_string()));
+/// ```
+pub fn logical_shift_right_rust(
+    binary_input: &str,
+    size: usize,
+    amount: usize,
+) -> Result<String, TwosComplementError> {
+    let bits = sign_extend_bits(binary_input, size)?;
+    let amount = amount.min(size);
+    Ok("0".repeat(amount) + &bits[..size - amount])
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn logical_shift_right(
+    binary_input: &str,
+    size: usize,
+    amount: usize,
+) -> Result<String, String> {
+    logical_shift_right_rust(binary_input, size, amount).map_err(|e| e.to_string())
+}
+
+/// Shifts a two's complement binary number right, replicating the sign bit.
+///
+/// The operand is sign-extended to `size` bits first. This divides by a power of two rounding
+/// towards negative infinity, and shifting by `size` or more yields all copies of the sign bit.
+///
+/// # Examples
+///
+/// ```
+/// use rust::arithmetic_shift_right_rust;
+///
+/// assert_eq!(arithmetic_shift_right_rust("1000", 4, 1), Ok("1100".to_string()));
+/// assert_eq!(arithmetic_shift_right_rust("0110", 4, 1), Ok("0011".to_string()));
+/// assert_eq!(arithmetic_shift_right_rust("1011", 4, 9), Ok("1111".to_string()));
+/// assert_eq!(arithmetic_shift_right_rust("0111", 4, 9), Ok("0000".to_string()));
+/// ```
+pub fn arithmetic_shift_right_rust(
+    binary_input: &str,
+    size: usize,
+    amount: usize,
+) -> Result<String, TwosComplementError> {
+    let bits = sign_extend_bits(binary_input, size)?;
+    let amount = amount.min(size);
+    Ok(bits[..1].repeat(amount) + &bits[..size - amount])
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn arithmetic_shift_right(
+    binary_input: &str,
+    size: usize,
+    amount: usize,
+) -> Result<String, String> {
+    arithmetic_shift_right_rust(binary_input, size, amount).map_err(|e| e.to_string())
+}
+
+/// Checks that the input is a binary string of exactly `size` bits
+fn validate_width(binary_input: &str, size: usize) -> Result<(), TwosComplementError> {
+    if size == 0 {
+        return Err(TwosComplementError::InvalidSize);
+    }
+    validate_binary(binary_input)?;
+    if binary_input.len() != size {
+        return Err(TwosComplementError::InvalidSize);
+    }
+    Ok(())
+}
+
+/// Reverses the order of the bits of a `size` bit binary string.
+///
+/// Unlike the shifts, the input is not sign-extended and must already be `size` bits long.
+///
+/// # Examples
+///
+/// ```
+/// use rust::bit_reverse_rust;
+/// use rust::TwosComplementError;
+///
+/// assert_eq!(bit_reverse_rust("1000", 4), Ok("0001".to_string()));
+/// assert_eq!(bit_reverse_rust("11010010", 8), Ok("01001011".to_string()));
+/// assert_eq!(bit_reverse_rust("100", 4), Err(TwosComplementError::InvalidSize));
+/// assert_eq!(bit_reverse_rust("1020", 4), Err(TwosComplementError::InvalidInput));
+/// ```
+pub fn bit_reverse_rust(binary_input: &str, size: usize) -> Result<String, TwosComplementError> {
+    validate_width(binary_input, size)?;
+    Ok(binary_input.chars().rev().collect())
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn bit_reverse(binary_input: &str, size: usize) -> Result<String, String> {
+    bit_reverse_rust(binary_input, size).map_err(|e| e.to_string())
+}
+
+/// Rotates the bits of a `size` bit binary string to the left, moving the bits shifted out on
+/// the left back in on the right.
+///
+/// The input must be exactly `size` bits long and the amount is taken modulo `size`.
+///
+/// # Examples
+///
+/// ```
+/// use rust::rotate_left_rust;
+/// use rust::TwosComplementError;
+///
+/// assert_eq!(rotate_left_rust("1000", 4, 1), Ok("0001".to_string()));
+/// assert_eq!(rotate_left_rust("1011", 4, 2), Ok("1110".to_string()));
+/// assert_eq!(rotate_left_rust("1011", 4, 6), Ok("1110".to_string()));
+/// assert_eq!(rotate_left_rust("1011", 4, 4), Ok("1011".to_string()));
+/// assert_eq!(rotate_left_rust("1011", 0, 1), Err(TwosComplementError::InvalidSize));
+/// ```
+pub fn rotate_left_rust(
+    binary_input: &str,
+    size: usize,
+    amount: usize,
+) -> Result<String, TwosComplementError> {
+    validate_width(binary_input, size)?;
+    let amount = amount % size;
+    Ok(binary_input[amount..].to_string() + &binary_input[..amount])
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn rotate_left(binary_input: &str, size: usize, amount: usize) -> Result<String, String> {
+    rotate_left_rust(binary_input, size, amount).map_err(|e| e.to_string())
+}
+
+/// Rotates the bits of a `size` bit binary string to the right, moving the bits shifted out on
+/// the right back in on the left.
+///
+/// The input must be exactly `size` bits long and the amount is taken modulo `size`.
+///
+/// # Examples
+///
+/// ```
+/// use rust::rotate_right_rust;
+///
+/// assert_eq!(rotate_right_rust("0001", 4, 1), Ok("1000".to_string()));
+/// assert_eq!(rotate_right_rust("1011", 4, 1), Ok("1101".to_string()));
+/// assert_eq!(rotate_right_rust("1011", 4, 5), Ok("1101".to_string()));
+/// ```
+pub fn rotate_right_rust(
+    binary_input: &str,
+    size: usize,
+    amount: usize,
+) -> Result<String, TwosComplementError> {
+    validate_width(binary_input, size)?;
+    let amount = amount % size;
+    rotate_left_rust(binary_input, size, size - amount)
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn rotate_right(binary_input: &str, size: usize, amount: usize) -> Result<String, String> {
+    rotate_right_rust(binary_input, size, amount).map_err(|e| e.to_string())
+}
+
+/// Converts a one's complement binary string to its decimal value.
+///
+/// Negative numbers are the bitwise inverse of their magnitude, so there are two zeros: all 0s
+/// (+0) and all 1s (-0). Both decode to 0.
+///
+/// ```
+/// use rust::calculate_ones_complement_rust;
+/// use rust::TwosComplementError;
+///
+/// assert_eq!(calculate_ones_complement_rust("1101"), Ok(-2));
+/// assert_eq!(calculate_ones_complement_rust("0101"), Ok(5));
+/// assert_eq!(calculate_ones_complement_rust("0000"), Ok(0));
+/// assert_eq!(calculate_ones_complement_rust("1111"), Ok(0));
+/// assert_eq!(
+///     calculate_ones_complement_rust("1021"),
+///     Err(TwosComplementError::InvalidInput)
+/// );
+/// ```
+pub fn calculate_ones_complement_rust(binary_input: &str) -> Result<i32, TwosComplementError> {
+    validate_binary(binary_input)?;
+
+    if binary_input.starts_with('1') {
+        let magnitude = i32::from_str_radix(&invert_bits(binary_input), 2)?;
+        Ok(-magnitude)
+    } else {
+        Ok(i32::from_str_radix(binary_input, 2)?)
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn calculate_ones_complement(binary_input: &str) -> Result<i32, String> {
+    calculate_ones_complement_rust(binary_input).map_err(|e| e.to_string())
+}
+
+/// Converts a sign-magnitude binary string to its decimal value.
+///
+/// The first bit is the sign and the remaining bits are the magnitude, so there are two
+/// zeros: `0000` (+0) and `1000` (-0). Both decode to 0.
+///
+/// ```
+/// use rust::calculate_sign_magnitude_rust;
+/// use rust::TwosComplementError;
+///
+/// assert_eq!(calculate_sign_magnitude_rust("1101"), Ok(-5));
+/// assert_eq!(calculate_sign_magnitude_rust("0101"), Ok(5));
+/// assert_eq!(calculate_sign_magnitude_rust("1000"), Ok(0));
+/// assert_eq!(calculate_sign_magnitude_rust("1"), Ok(0));
+/// assert_eq!(
+///     calculate_sign_magnitude_rust(""),
+///     Err(TwosComplementError::InvalidInput)
+/// );
+/// ```
+pub fn calculate_sign_magnitude_rust(binary_input: &str) -> Result<i32, TwosComplementError> {
+    validate_binary(binary_input)?;
+
+    let (sign, magnitude_bits) = binary_input.split_at(1);
+    let magnitude = if magnitude_bits.is_empty() {
+        0
+    } else {
+        i32::from_str_radix(magnitude_bits, 2)?
+    };
+
+    if sign == "1" {
+        Ok(-magnitude)
+    } else {
+        Ok(magnitude)
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn calculate_sign_magnitude(binary_input: &str) -> Result<i32, String> {
+    calculate_sign_magnitude_rust(binary_input).map_err(|e| e.to_string())
+}
+
+/// Checks that the magnitude of `decimal` fits in `size - 1` bits, as both one's complement
+/// and sign-magnitude require
+fn validate_symmetric_range(decimal: i64, size: usize) -> Result<(), TwosComplementError> {
+    if size == 0 {
+        return Err(TwosComplementError::InvalidSize);
+    }
+    if size <= 64 && decimal.unsigned_abs() > (1u64 << (size - 1)) - 1 {
+        return Err(TwosComplementError::OverflowError);
+    }
+    Ok(())
+}
+
+/// Converts a decimal number to its one's complement representation in `size` bits.
+///
+/// The range is symmetric, from `-(2^(size-1) - 1)` to `2^(size-1) - 1`. Zero is always
+/// written as +0 (all 0s).
+///
+/// ```
+/// use rust::decimal_to_ones_complement_rust;
+/// use rust::TwosComplementError;
+///
+/// assert_eq!(decimal_to_ones_complement_rust(-2, 4), Ok("1101".to_string()));
+/// assert_eq!(decimal_to_ones_complement_rust(5, 4), Ok("0101".to_string()));
+/// assert_eq!(decimal_to_ones_complement_rust(0, 4), Ok("0000".to_string()));
+/// assert_eq!(decimal_to_ones_complement_rust(-7, 4), Ok("1000".to_string()));
+/// assert_eq!(
+///     decimal_to_ones_complement_rust(-8, 4),
+///     Err(TwosComplementError::OverflowError)
+/// );
+/// ```
+pub fn decimal_to_ones_complement_rust(
+    decimal: i64,
+    size: usize,
+) -> Result<String, TwosComplementError> {
+    validate_symmetric_range(decimal, size)?;
+
+    let magnitude = format!("{:0width$b}", decimal.unsigned_abs(), width = size);
+    if decimal < 0 {
+        Ok(invert_bits(&magnitude))
+    } else {
+        Ok(magnitude)
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn decimal_to_ones_complement(decimal: i32, size: usize) -> Result<String, String> {
+    decimal_to_ones_complement_rust(decimal.into(), size).map_err(|e| e.to_string())
+}
+
+/// Converts a decimal number to its sign-magnitude representation in `size` bits.
+///
+/// The range is symmetric, from `-(2^(size-1) - 1)` to `2^(size-1) - 1`. Zero is always
+/// written as +0 (all 0s).
+///
+/// ```
+/// use rust::decimal_to_sign_magnitude_rust;
+/// use rust::TwosComplementError;
+///
+/// assert_eq!(decimal_to_sign_magnitude_rust(-5, 4), Ok("1101".to_string()));
+/// assert_eq!(decimal_to_sign_magnitude_rust(5, 4), Ok("0101".to_string()));
+/// assert_eq!(decimal_to_sign_magnitude_rust(0, 4), Ok("0000".to_string()));
+/// assert_eq!(decimal_to_sign_magnitude_rust(0, 1), Ok("0".to_string()));
+/// assert_eq!(
+///     decimal_to_sign_magnitude_rust(8, 4),
+///     Err(TwosComplementError::OverflowError)
+/// );
+/// ```
+pub fn decimal_to_sign_magnitude_rust(
+    decimal: i64,
+    size: usize,
+) -> Result<String, TwosComplementError> {
+    validate_symmetric_range(decimal, size)?;
+
+    let sign = if decimal < 0 { '1' } else { '0' };
+    let magnitude = format!("{:0width$b}", decimal.unsigned_abs(), width = size - 1);
+    // A size of 1 only holds zero, whose empty magnitude formats as "0"
+    Ok(format!(
+        "{}{}",
+        sign,
+        &magnitude[magnitude.len() - (size - 1)..]
+    ))
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn decimal_to_sign_magnitude(decimal: i32, size: usize) -> Result<String, String> {
+    decimal_to_sign_magnitude_rust(decimal.into(), size).map_err(|e| e.to_string())
+}
+
+/// Encodes `value + bias` as a `size` bit unsigned binary string
+fn encode_excess(decimal: i64, size: usize, bias: i128) -> Result<String, TwosComplementError> {
+    if size == 0 || size > 64 {
+        return Err(TwosComplementError::InvalidSize);
+    }
+    let encoded = decimal as i128 + bias;
+    if encoded < 0 || encoded >= 1i128 << size {
+        return Err(TwosComplementError::OverflowError);
+    }
+    Ok(format!("{:0width$b}", encoded, width = size))
+}
+
+/// Decodes an unsigned binary string and subtracts `bias`
+fn decode_excess(binary_input: &str, bias: i128) -> Result<i64, TwosComplementError> {
+    validate_binary(binary_input)?;
+    if binary_input.len() > 64 {
+        return Err(TwosComplementError::InvalidSize);
+    }
+    let encoded = u64::from_str_radix(binary_input, 2)? as i128;
+    i64::try_from(encoded - bias).map_err(|_| TwosComplementError::OverflowError)
+}
+
+/// Converts a decimal number to its excess-K (offset binary) representation with the usual
+/// bias of `2^(size - 1)`.
+///
+/// The stored bits are the unsigned binary form of `decimal + K`, so with 8 bits excess-128
+/// covers -128 to 127 and, unlike two's complement, the bit patterns sort in the same order as
+/// the values. IEEE 754 stores its exponent this way, with a bias one lower; use
+/// [`decimal_to_excess_k_with_bias_rust`] for that. Sizes from 1 to 64 bits are supported.
+///
+/// # Examples
+///
+/// ```
+/// use rust::decimal_to_excess_k_rust;
+/// use rust::TwosComplementError;
+///
+/// assert_eq!(decimal_to_excess_k_rust(0, 8), Ok("10000000".to_string()));
+/// assert_eq!(decimal_to_excess_k_rust(-128, 8), Ok("00000000".to_string()));
+/// assert_eq!(decimal_to_excess_k_rust(127, 8), Ok("11111111".to_string()));
+/// assert_eq!(decimal_to_excess_k_rust(-3, 4), Ok("0101".to_string()));
+/// assert_eq!(decimal_to_excess_k_rust(i64::MIN, 64), Ok("0".repeat(64)));
+/// assert_eq!(decimal_to_excess_k_rust(128, 8), Err(TwosComplementError::OverflowError));
+/// assert_eq!(decimal_to_excess_k_rust(0, 0), Err(TwosComplementError::InvalidSize));
+/// ```
+pub fn decimal_to_excess_k_rust(decimal: i64, size: usize) -> Result<String, TwosComplementError> {
+    if size == 0 || size > 64 {
+        return Err(TwosComplementError::InvalidSize);
+    }
+    encode_excess(decimal, size, 1i128 << (size - 1))
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn decimal_to_excess_k(decimal: i32, size: usize) -> Result<String, String> {
+    decimal_to_excess_k_rust(decimal.into(), size).map_err(|e| e.to_string())
+}
+
+/// Converts an excess-K binary string back to decimal, with the bias `2^(n - 1)` taken from the
+/// input length `n`.
+///
+/// # Examples
+///
+/// ```
+/// use rust::excess_k_to_decimal_rust;
+/// use rust::TwosComplementError;
+///
+/// assert_eq!(excess_k_to_decimal_rust("10000000"), Ok(0));
+/// assert_eq!(excess_k_to_decimal_rust("00000000"), Ok(-128));
+/// assert_eq!(excess_k_to_decimal_rust("0101"), Ok(-3));
+/// assert_eq!(excess_k_to_decimal_rust(""), Err(TwosComplementError::InvalidInput));
+/// assert_eq!(excess_k_to_decimal_rust("10a"), Err(TwosComplementError::InvalidInput));
+/// ```
+pub fn excess_k_to_decimal_rust(binary_input: &str) -> Result<i64, TwosComplementError> {
+    validate_binary(binary_input)?;
+    if binary_input.len() > 64 {
+        return Err(TwosComplementError::InvalidSize);
+    }
+    decode_excess(binary_input, 1i128 << (binary_input.len() - 1))
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn excess_k_to_decimal(binary_input: &str) -> Result<i64, String> {
+    excess_k_to_decimal_rust(binary_input).map_err(|e| e.to_string())
+}
+
+/// Converts a decimal number to excess-K with an explicit bias.
+///
+/// # Examples
+///
+/// ```
+/// use rust::decimal_to_excess_k_with_bias_rust;
+/// use rust::TwosComplementError;
+///
+/// // The exponent of 1.0 in an IEEE 754 single uses excess-127
+/// assert_eq!(decimal_to_excess_k_with_bias_rust(0, 8, 127), Ok("01111111".to_string()));
+/// assert_eq!(decimal_to_excess_k_with_bias_rust(128, 8, 127), Ok("11111111".to_string()));
+/// assert_eq!(
+///     decimal_to_excess_k_with_bias_rust(-128, 8, 127),
+///     Err(TwosComplementError::OverflowError)
+/// );
+/// ```
+pub fn decimal_to_excess_k_with_bias_rust(
+    decimal: i64,
+    size: usize,
+    bias: i64,
+) -> Result<String, TwosComplementError> {
+    encode_excess(decimal, size, bias.into())
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn decimal_to_excess_k_with_bias(
+    decimal: i32,
+    size: usize,
+    bias: i32,
+) -> Result<String, String> {
+    decimal_to_excess_k_with_bias_rust(decimal.into(), size, bias.into()).map_err(|e| e.to_string())
+}
+
+/// Converts an excess-K binary string with an explicit bias back to decimal.
+///
+/// # Examples
+///
+/// ```
+/// use rust::excess_k_to_decimal_with_bias_rust;
+///
+/// assert_eq!(excess_k_to_decimal_with_bias_rust("01111111", 127), Ok(0));
+/// assert_eq!(excess_k_to_decimal_with_bias_rust("00000001", 127), Ok(-126));
+/// assert_eq!(excess_k_to_decimal_with_bias_rust("0000", -3), Ok(3));
+/// ```
+pub fn excess_k_to_decimal_with_bias_rust(
+    binary_input: &str,
+    bias: i64,
+) -> Result<i64, TwosComplementError> {
+    decode_excess(binary_input, bias.into())
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn excess_k_to_decimal_with_bias(binary_input: &str, bias: i32) -> Result<i64, String> {
+    excess_k_to_decimal_with_bias_rust(binary_input, bias.into()).map_err(|e| e.to_string())
+}
+
+//
+// Bit-level encodings
+//
+
+/// Converts a binary string to its reflected binary (Gray) code of the same width.
+///
+/// Each Gray bit is the XOR of the binary bit and the bit to its left, `g = b ^ (b >> 1)`.
+///
+/// # Examples
+///
+/// ```
+/// use rust::binary_to_gray_rust;
+/// use rust::TwosComplementError;
+///
+/// assert_eq!(binary_to_gray_rust("0111"), Ok("0100".to_string()));
+/// assert_eq!(binary_to_gray_rust("1000"), Ok("1100".to_string()));
+/// assert_eq!(binary_to_gray_rust("0"), Ok("0".to_string()));
+/// assert_eq!(binary_to_gray_rust("01a1"), Err(TwosComplementError::InvalidInput));
+/// ```
+pub fn binary_to_gray_rust(binary_input: &str) -> Result<String, TwosComplementError> {
+    validate_binary(binary_input)?;
+
+    let mut previous = '0';
+    Ok(binary_input
+        .chars()
+        .map(|bit| {
+            let gray = if bit == previous { '0' } else { '1' };
+            previous = bit;
+            gray
+        })
+        .collect())
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn binary_to_gray(binary_input: &str) -> Result<String, String> {
+    binary_to_gray_rust(binary_input).map_err(|e| e.to_string())
+}
+
+/// Converts a reflected binary (Gray) code back to plain binary of the same width.
+///
+/// Each binary bit is the running XOR of all Gray bits up to and including it.
+///
+/// # Examples
+///
+/// ```
+/// use rust::{binary_to_gray_rust, gray_to_binary_rust};
+/// use rust::TwosComplementError;
+///
+/// assert_eq!(gray_to_binary_rust("0100"), Ok("0111".to_string()));
+/// assert_eq!(gray_to_binary_rust("1100"), Ok("1000".to_string()));
+///
+/// // Round trip
+/// let gray = binary_to_gray_rust("0111").unwrap();
+/// assert_eq!(gray_to_binary_rust(&gray), Ok("0111".to_string()));
+///
+/// assert_eq!(gray_to_binary_rust(""), Err(TwosComplementError::InvalidInput));
+/// ```
+pub fn gray_to_binary_rust(gray_input: &str) -> Result<String, TwosComplementError> {
+    validate_binary(gray_input)?;
+
+    let mut running = '0';
+    Ok(gray_input
+        .chars()
+        .map(|bit| {
+            if bit == '1' {
+                running = if running == '0' { '1' } else { '0' };
+            }
+            running
+        })
+        .collect())
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn gray_to_binary(gray_input: &str) -> Result<String, String> {
+    gray_to_binary_rust(gray_input).map_err(|e| e.to_string())
+}
+
+/// Returns the IEEE 754 bit pattern of a floating point number.
+///
+/// The bits are laid out as sign, exponent and mantissa: 1 + 8 + 23 bits for single
+/// precision, where the value is first rounded to an `f32`, or 1 + 11 + 52 bits for double
+/// precision.
+///
+/// # Arguments
+///
+/// * `value` - The number to inspect.
+/// * `double` - `true` for the 64-bit double precision layout, `false` for 32-bit single
+///   precision.
+///
+/// # Examples
+///
+/// ```
+/// use rust::float_to_ieee754_rust;
+///
+/// assert_eq!(
+///     float_to_ieee754_rust(1.0, false),
+///     format!("0{}{}", "01111111", "0".repeat(23))
+/// );
+/// assert_eq!(
+///     float_to_ieee754_rust(1.0, true),
+///     format!("0{}{}", "01111111111", "0".repeat(52))
+/// );
+///
+/// // Negative zero only has the sign bit set
+/// assert_eq!(float_to_ieee754_rust(-0.0, true), format!("1{}", "0".repeat(63)));
+///
+/// // Quiet NaN has all exponent bits and the top mantissa bit set
+/// assert_eq!(
+///     float_to_ieee754_rust(f64::NAN, false),
+///     format!("0{}1{}", "1".repeat(8), "0".repeat(22))
+/// );
+/// ```
+pub fn float_to_ieee754_rust(value: f64, double: bool) -> String {
+    if double {
+        format!("{:064b}", value.to_bits())
+    } else {
+        format!("{:032b}", (value as f32).to_bits())
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn float_to_ieee754(value: f64, double: bool) -> String {
+    float_to_ieee754_rust(value, double)
+}
+
+/// Converts an IEEE 754 bit pattern back to its floating point value.
+///
+/// A 32-bit pattern is read as single precision and a 64-bit pattern as double precision.
+///
+/// # Examples
+///
+/// ```
+/// use rust::ieee754_to_float_rust;
+/// use rust::TwosComplementError;
+///
+/// assert_eq!(
+///     ieee754_to_float_rust("00111111100000000000000000000000"),
+///     Ok(1.0)
+/// );
+///
+/// let negative_zero = ieee754_to_float_rust(&format!("1{}", "0".repeat(63))).unwrap();
+/// assert_eq!(negative_zero, 0.0);
+/// assert!(negative_zero.is_sign_negative());
+///
+/// let nan = ieee754_to_float_rust(&format!("0{}1{}", "1".repeat(11), "0".repeat(51))).unwrap();
+/// assert!(nan.is_nan());
+///
+/// assert_eq!(ieee754_to_float_rust("0101"), Err(TwosComplementError::InvalidSize));
+/// assert_eq!(
+///     ieee754_to_float_rust(&"2".repeat(32)),
+///     Err(TwosComplementError::InvalidInput)
+/// );
+/// ```
+pub fn ieee754_to_float_rust(bits: &str) -> Result<f64, TwosComplementError> {
+    validate_binary(bits)?;
+
+    match bits.len() {
+        32 => {
+            let pattern = u32::from_str_radix(bits, 2).map_err(TwosComplementError::ParseError)?;
+            Ok(f32::from_bits(pattern) as f64)
+        }
+        64 => {
+            let pattern = u64::from_str_radix(bits, 2).map_err(TwosComplementError::ParseError)?;
+            Ok(f64::from_bits(pattern))
+        }
+        _ => Err(TwosComplementError::InvalidSize),
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn ieee754_to_float(bits: &str) -> Result<f64, String> {
+    ieee754_to_float_rust(bits).map_err(|e| e.to_string())
+}
+
+/// Counts the number of set bits (the Hamming weight) of a binary string.
+///
+/// # Examples
+///
+/// ```
+/// use rust::population_count_rust;
+/// use rust::TwosComplementError;
+///
+/// assert_eq!(population_count_rust("1011"), Ok(3));
+/// assert_eq!(population_count_rust("0000"), Ok(0));
+/// assert_eq!(population_count_rust("10x1"), Err(TwosComplementError::InvalidInput));
+/// ```
+pub fn population_count_rust(binary_input: &str) -> Result<u32, TwosComplementError> {
+    validate_binary(binary_input)?;
+    Ok(binary_input.chars().filter(|&bit| bit == '1').count() as u32)
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn population_count(binary_input: &str) -> Result<u32, String> {
+    population_count_rust(binary_input).map_err(|e| e.to_string())
+}
+
+/// Returns the parity bit to append to a binary string.
+///
+/// With even parity the bit makes the total number of 1s, including the parity bit, even. With
+/// odd parity it makes the total odd.
+///
+/// # Examples
+///
+/// ```
+/// use rust::parity_bit_rust;
+/// use rust::TwosComplementError;
+///
+/// assert_eq!(parity_bit_rust("1011", true), Ok('1'));
+/// assert_eq!(parity_bit_rust("1011", false), Ok('0'));
+/// assert_eq!(parity_bit_rust("1001", true), Ok('0'));
+/// assert_eq!(parity_bit_rust("", true), Err(TwosComplementError::InvalidInput));
+/// ```
+pub fn parity_bit_rust(binary_input: &str, even: bool) -> Result<char, TwosComplementError> {
+    let odd_ones = population_count_rust(binary_input)? % 2 == 1;
+    if odd_ones == even {
+        Ok('1')
+    } else {
+        Ok('0')
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn parity_bit(binary_input: &str, even: bool) -> Result<char, String> {
+    parity_bit_rust(binary_input, even).map_err(|e| e.to_string())
+}
+
+/// Encodes a decimal number as packed binary-coded decimal (BCD).
+///
+/// Each decimal digit becomes a 4-bit nibble, most significant digit first.
+///
+/// # Examples
+///
+/// ```
+/// use rust::decimal_to_bcd_rust;
+///
+/// assert_eq!(decimal_to_bcd_rust(1234), "0001001000110100");
+/// assert_eq!(decimal_to_bcd_rust(0), "0000");
+/// assert_eq!(decimal_to_bcd_rust(90), "10010000");
+/// ```
+pub fn decimal_to_bcd_rust(decimal: u32) -> String {
+    decimal
+        .to_string()
+        .chars()
+        .map(|digit| format!("{:04b}", digit.to_digit(10).unwrap_or(0)))
+        .collect()
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn decimal_to_bcd(decimal: u32) -> String {
+    decimal_to_bcd_rust(decimal)
+}
+
+/// Decodes a packed binary-coded decimal (BCD) string.
+///
+/// The input must be a whole number of 4-bit nibbles, each between `0000` and `1001`.
+///
+/// # Examples
+///
+/// ```
+/// use rust::bcd_to_decimal_rust;
+/// use rust::TwosComplementError;
+///
+/// assert_eq!(bcd_to_decimal_rust("0001001000110100"), Ok(1234));
+/// assert_eq!(bcd_to_decimal_rust("10010000"), Ok(90));
+/// assert_eq!(bcd_to_decimal_rust("1010"), Err(TwosComplementError::InvalidInput));
+/// assert_eq!(bcd_to_decimal_rust("00010"), Err(TwosComplementError::InvalidInput));
+/// assert_eq!(
+///     bcd_to_decimal_rust(&"1001".repeat(10)),
+///     Err(TwosComplementError::OverflowError)
+/// );
+/// ```
+pub fn bcd_to_decimal_rust(bcd: &str) -> Result<u32, TwosComplementError> {
+    validate_binary(bcd)?;
+    if !bcd.len().is_multiple_of(4) {
+        return Err(TwosComplementError::InvalidInput);
+    }
+
+    let mut decimal: u32 = 0;
+    for nibble in bcd.as_bytes().chunks(4) {
+        let digit = nibble
+            .iter()
+            .fold(0u32, |value, &bit| (value << 1) | u32::from(bit == b'1'));
+        if digit > 9 {
+            return Err(TwosComplementError::InvalidInput);
+        }
+        decimal = decimal
+            .checked_mul(10)
+            .and_then(|value| value.checked_add(digit))
+            .ok_or(TwosComplementError::OverflowError)?;
+    }
+    Ok(decimal)
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn bcd_to_decimal(bcd: &str) -> Result<u32, String> {
+    bcd_to_decimal_rust(bcd).map_err(|e| e.to_string())
+}
+
+/// Decodes a two's complement word as a fixed-point number in Q format.
+///
+/// The bits are read as a signed integer and scaled by `2^-fractional_bits`, so a word of
+/// `m + n` bits with `n` fractional bits holds values in steps of `2^-n`. Words of up to 64 bits
+/// are supported, and `fractional_bits` must be smaller than the word length.
+///
+/// # Examples
+///
+/// ```
+/// use rust::qformat_decode_rust;
+/// use rust::TwosComplementError;
+///
+/// assert_eq!(qformat_decode_rust("0100", 2), Ok(1.0));
+/// assert_eq!(qformat_decode_rust("1100", 3), Ok(-0.5));
+/// assert_eq!(qformat_decode_rust("1000", 3), Ok(-1.0));
+/// assert_eq!(qformat_decode_rust("0111", 3), Ok(0.875));
+/// assert_eq!(qformat_decode_rust("0100", 0), Ok(4.0));
+/// assert_eq!(qformat_decode_rust("0100", 4), Err(TwosComplementError::InvalidSize));
+/// assert_eq!(qformat_decode_rust("0120", 2), Err(TwosComplementError::InvalidInput));
+/// ```
+pub fn qformat_decode_rust(
+    binary_input: &str,
+    fractional_bits: usize,
+) -> Result<f64, TwosComplementError> {
+    validate_binary(binary_input)?;
+    let size = binary_input.len();
+    if size > 64 || fractional_bits >= size {
+        return Err(TwosComplementError::InvalidSize);
+    }
+
+    // Shift the sign bit into bit 63 so the arithmetic shift back sign-extends the word
+    let bits = u64::from_str_radix(binary_input, 2)?;
+    let integer = ((bits << (64 - size)) as i64) >> (64 - size);
+
+    Ok(integer as f64 / 2f64.powi(fractional_bits as i32))
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn qformat_decode(binary_input: &str, fractional_bits: usize) -> Result<f64, String> {
+    qformat_decode_rust(binary_input, fractional_bits).map_err(|e| e.to_string())
+}
+
+/// Encodes a number as a `total_bits` wide two's complement word in Q format with
+/// `fractional_bits` fractional bits.
+///
+/// The value is rounded to the nearest multiple of `2^-fractional_bits`, with ties away from
+/// zero. Values outside the representable range are reported as `OverflowError` rather than
+/// saturated, and NaN is rejected as `InvalidInput`.
+///
+/// # Examples
+///
+/// ```
+/// use rust::qformat_encode_rust;
+/// use rust::TwosComplementError;
+///
+/// // Q1.3: one integer (sign) bit and three fractional bits
+/// assert_eq!(qformat_encode_rust(-0.5, 4, 3), Ok("1100".to_string()));
+/// assert_eq!(qformat_encode_rust(-1.0, 4, 3), Ok("1000".to_string()));
+/// assert_eq!(qformat_encode_rust(0.875, 4, 3), Ok("0111".to_string()));
+///
+/// // Q2.2
+/// assert_eq!(qformat_encode_rust(1.0, 4, 2), Ok("0100".to_string()));
+/// assert_eq!(qformat_encode_rust(1.3, 4, 2), Ok("0101".to_string()));
+///
+/// // Error cases
+/// assert_eq!(qformat_encode_rust(1.0, 4, 3), Err(TwosComplementError::OverflowError));
+/// assert_eq!(qformat_encode_rust(f64::INFINITY, 8, 4), Err(TwosComplementError::OverflowError));
+/// assert_eq!(qformat_encode_rust(f64::NAN, 8, 4), Err(TwosComplementError::InvalidInput));
+/// assert_eq!(qformat_encode_rust(0.5, 4, 4), Err(TwosComplementError::InvalidSize));
+/// ```
+pub fn qformat_encode_rust(
+    value: f64,
+    total_bits: usize,
+    fractional_bits: usize,
+) -> Result<String, TwosComplementError> {
+    fixed_point_bits(value, total_bits, fractional_bits, RoundMode::Nearest)
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn qformat_encode(
+    value: f64,
+    total_bits: usize,
+    fractional_bits: usize,
+) -> Result<String, String> {
+    qformat_encode_rust(value, total_bits, fractional_bits).map_err(|e| e.to_string())
+}
+
+/// How a value is rounded to the nearest representable fixed-point step.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RoundMode {
+    /// Round towards zero
+    Truncate,
+    /// Round to the nearest step, with ties away from zero
+    Nearest,
+    /// Round towards positive infinity
+    Ceil,
+    /// Round towards negative infinity
+    Floor,
+}
+
+/// Scales, rounds and range checks a value, returning the two's complement bits of the
+/// `total_bits` wide fixed-point word
+fn fixed_point_bits(
+    value: f64,
+    total_bits: usize,
+    fractional_bits: usize,
+    round: RoundMode,
+) -> Result<String, TwosComplementError> {
+    if total_bits == 0 || total_bits > 64 || fractional_bits >= total_bits {
+        return Err(TwosComplementError::InvalidSize);
+    }
+    if value.is_nan() {
+        return Err(TwosComplementError::InvalidInput);
+    }
+
+    let scaled = value * 2f64.powi(fractional_bits as i32);
+    let scaled = match round {
+        RoundMode::Truncate => scaled.trunc(),
+        RoundMode::Nearest => scaled.round(),
+        RoundMode::Ceil => scaled.ceil(),
+        RoundMode::Floor => scaled.floor(),
+    };
+    // Powers of two are exact in f64, unlike 2^63 - 1, so compare against the exclusive bound
+    let limit = 2f64.powi(total_bits as i32 - 1);
+    if scaled >= limit || scaled < -limit {
+        return Err(TwosComplementError::OverflowError);
+    }
+
+    decimal_to_twos_complement_rust(scaled as i64, total_bits)
+}
+
+/// Converts a fractional decimal number to a two's complement binary fixed-point number with a
+/// binary point.
+///
+/// The output has `integer_bits` bits before the `.`, including the sign bit, and
+/// `fractional_bits` after it, which is the same word as [`qformat_encode_rust`] produces. Any
+/// part of the value finer than `2^-fractional_bits` is lost according to `round`, so for
+/// example 0.1 has no exact binary representation and is always approximated.
+///
+/// # Examples
+///
+/// ```
+/// use rust::{decimal_fraction_to_binary_rust, RoundMode};
+/// use rust::TwosComplementError;
+///
+/// assert_eq!(
+///     decimal_fraction_to_binary_rust(5.625, 4, 3, RoundMode::Nearest),
+///     Ok("0101.101".to_string())
+/// );
+/// assert_eq!(
+///     decimal_fraction_to_binary_rust(-5.625, 4, 3, RoundMode::Nearest),
+///     Ok("1010.011".to_string())
+/// );
+///
+/// // 0.1 lies between 0.0625 and 0.125 with four fractional bits
+/// assert_eq!(
+///     decimal_fraction_to_binary_rust(0.1, 2, 4, RoundMode::Truncate),
+///     Ok("00.0001".to_string())
+/// );
+/// assert_eq!(
+///     decimal_fraction_to_binary_rust(0.1, 2, 4, RoundMode::Nearest),
+///     Ok("00.0010".to_string())
+/// );
+/// assert_eq!(
+///     decimal_fraction_to_binary_rust(0.1, 2, 4, RoundMode::Ceil),
+///     Ok("00.0010".to_string())
+/// );
+/// assert_eq!(
+///     decimal_fraction_to_binary_rust(-0.1, 2, 4, RoundMode::Floor),
+///     Ok("11.1110".to_string())
+/// );
+/// assert_eq!(
+///     decimal_fraction_to_binary_rust(-0.1, 2, 4, RoundMode::Truncate),
+///     Ok("11.1111".to_string())
+/// );
+///
+/// // Without fractional bits there is no binary point
+/// assert_eq!(
+///     decimal_fraction_to_binary_rust(5.5, 4, 0, RoundMode::Floor),
+///     Ok("0101".to_string())
+/// );
+///
+/// // Error cases
+/// assert_eq!(
+///     decimal_fraction_to_binary_rust(8.0, 4, 3, RoundMode::Nearest),
+///     Err(TwosComplementError::OverflowError)
+/// );
+/// assert_eq!(
+///     decimal_fraction_to_binary_rust(7.97, 4, 3, RoundMode::Nearest),
+///     Err(TwosComplementError::OverflowError)
+/// );
+/// assert_eq!(
+///     decimal_fraction_to_binary_rust(0.5, 0, 3, RoundMode::Nearest),
+///     Err(TwosComplementError::InvalidSize)
+/// );
+/// ```
+pub fn decimal_fraction_to_binary_rust(
+    value: f64,
+    integer_bits: usize,
+    fractional_bits: usize,
+    round: RoundMode,
+) -> Result<String, TwosComplementError> {
+    if integer_bits == 0 {
+        return Err(TwosComplementError::InvalidSize);
+    }
+
+    let mut bits = fixed_point_bits(
+        value,
+        integer_bits + fractional_bits,
+        fractional_bits,
+        round,
+    )?;
+    if fractional_bits > 0 {
+        bits.insert(integer_bits, '.');
+    }
+    Ok(bits)
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn decimal_fraction_to_binary(
+    value: f64,
+    integer_bits: usize,
+    fractional_bits: usize,
+    round: RoundMode,
+) -> Result<String, String> {
+    decimal_fraction_to_binary_rust(value, integer_bits, fractional_bits, round)
+        .map_err(|e| e.to_string())
+}
+
+/// Encodes data bits with a Hamming single-error-correcting code.
+///
+/// Four data bits give the classic Hamming(7,4) codeword. Parity bits sit at the positions
+/// that are powers of two (1, 2, 4, 8, ... counting from 1 on the left) and the data bits fill
+/// the others in order. Longer inputs use as many parity bits as needed, so 5 to 11 data bits
+/// give a (shortened) Hamming(15,11) codeword, and so on.
+///
+/// # Examples
+///
+/// ```
+/// use rust::hamming_encode_rust;
+/// use rust::TwosComplementError;
+///
+/// assert_eq!(hamming_encode_rust("1011"), Ok("0110011".to_string()));
+/// assert_eq!(hamming_encode_rust("0000"), Ok("0000000".to_string()));
+/// assert_eq!(hamming_encode_rust("11111111111"), Ok("111111111111111".to_string()));
+/// assert_eq!(hamming_encode_rust("1"), Ok("111".to_string()));
+/// assert_eq!(hamming_encode_rust("10a1"), Err(TwosComplementError::InvalidInput));
+/// ```
+pub fn hamming_encode_rust(data_bits: &str) -> Result<String, TwosComplementError> {
+    validate_binary(data_bits)?;
+
+    let mut parity_bits = 0;
+    while (1usize << parity_bits) < data_bits.len() + parity_bits + 1 {
+        parity_bits += 1;
+    }
+    let length = data_bits.len() + parity_bits;
+
+    let mut code = vec![false; length + 1];
+    let mut data = data_bits.chars().map(|bit| bit == '1');
+    for (position, bit) in code.iter_mut().enumerate().skip(1) {
+        if !position.is_power_of_two() {
+            *bit = data.next().unwrap_or(false);
+        }
+    }
+
+    // Each parity bit makes the XOR over the positions sharing its bit even
+    let syndrome = hamming_syndrome(&code);
+    for parity in 0..parity_bits {
+        code[1 << parity] = syndrome & (1 << parity) != 0;
+    }
+
+    Ok(code[1..]
+        .iter()
+        .map(|&bit| if bit { '1' } else { '0' })
+        .collect())
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn hamming_encode(data_bits: &str) -> Result<String, String> {
+    hamming_encode_rust(data_bits).map_err(|e| e.to_string())
+}
+
+/// XOR of the 1-based positions of all set bits, ignoring index 0
+fn hamming_syndrome(code: &[bool]) -> usize {
+    code.iter()
+        .enumerate()
+        .filter(|&(_, &bit)| bit)
+        .fold(0, |syndrome, (position, _)| syndrome ^ position)
+}
+
+/// Decodes a Hamming codeword produced by [`hamming_encode_rust`], correcting a single flipped
+/// bit.
+///
+/// Returns the data bits and the 1-based position of the bit that was corrected, or `None` if
+/// the codeword was clean. Two or more flipped bits cannot be told apart from a single one and
+/// are miscorrected, except when the syndrome points past the end of a shortened codeword, which
+/// is reported as `InvalidInput`. Codewords must be at least 3 bits long.
+///
+/// # Examples
+///
+/// ```
+/// use rust::hamming_decode_rust;
+/// use rust::TwosComplementError;
+///
+/// assert_eq!(hamming_decode_rust("0110011"), Ok(("1011".to_string(), None)));
+///
+/// // Flip the fifth bit and let the decoder find it
+/// assert_eq!(hamming_decode_rust("0110111"), Ok(("1011".to_string(), Some(5))));
+///
+/// // Errors in parity bits are reported too
+/// assert_eq!(hamming_decode_rust("1110011"), Ok(("1011".to_string(), Some(1))));
+///
+/// assert_eq!(hamming_decode_rust("01"), Err(TwosComplementError::InvalidSize));
+/// assert_eq!(hamming_decode_rust("0112011"), Err(TwosComplementError::InvalidInput));
+/// ```
+pub fn hamming_decode_rust(code: &str) -> Result<(String, Option<usize>), TwosComplementError> {
+    validate_binary(code)?;
+    if code.len() < 3 {
+        return Err(TwosComplementError::InvalidSize);
+    }
+
+    let mut bits: Vec<bool> = core::iter::once(false)
+        .chain(code.chars().map(|bit| bit == '1'))
+        .collect();
+
+    let error_position = match hamming_syndrome(&bits) {
+        0 => None,
+        position if position < bits.len() => {
+            bits[position] = !bits[position];
+            Some(position)
+        }
+        _ => return Err(TwosComplementError::InvalidInput),
+    };
+
+    let data = bits
+        .iter()
+        .enumerate()
+        .skip(1)
+        .filter(|(position, _)| !position.is_power_of_two())
+        .map(|(_, &bit)| if bit { '1' } else { '0' })
+        .collect();
+
+    Ok((data, error_position))
+}
+
+/// Wasm entry point for [`hamming_decode_rust`] returning `[data, position]`, with an empty
+/// position when no error was corrected.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn hamming_decode(code: &str) -> Result<Vec<String>, String> {
+    match hamming_decode_rust(code) {
+        Ok((data, position)) => Ok(vec![
+            data,
+            position.map(|p| p.to_string()).unwrap_or_default(),
+        ]),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+//
+// Geodetic coordinates
+//
+
+/// Custom error type for geodetic and UTM calculations using `thiserror`
+#[derive(Debug, Error, PartialEq)]
+pub enum GeoError {
+    #[error("Invalid latitude: {0}. Latitude must be between -90 and 90 degrees.")]
+    InvalidLatitude(f64),
+    #[error("Invalid longitude: {0}. Longitude must be between -180 and 180 degrees.")]
+    InvalidLongitude(f64),
+    #[error("Calculation did not converge. The points may be nearly antipodal.")]
+    NonConvergence,
+    #[error("Invalid input: {0}")]
+    InvalidInput(String),
+    #[error("Invalid precision: {0}. Precision must be between 0 and 5 digits.")]
+    InvalidPrecision(u8),
+    #[error("Invalid zone: {0}. UTM zones are numbered from 1 to 60.")]
+    InvalidZone(u32),
+    #[error("Invalid latitude band: {0}. Bands run from 'C' to 'X', excluding 'I' and 'O'.")]
+    InvalidBand(char),
+    #[error("{0}")]
+    CalculationError(String),
+}
+
+/// Error type of the UTM and MGRS functions.
+///
+/// This used to be a separate enum and is now an alias of [`GeoError`], so existing code that
+/// names `UTMZoneError` or its variants keeps working and callers have a single type to match
+/// on.
+///
+/// ```
+/// use rust::{calculate_utm_zone, GeoError, UTMZoneError};
+///
+/// let err: UTMZoneError = calculate_utm_zone(100.0, 0.0).unwrap_err();
+/// assert_eq!(err, GeoError::InvalidLatitude(100.0));
+/// assert!(matches!(err, UTMZoneError::InvalidLatitude(_)));
+/// ```
+pub type UTMZoneError = GeoError;
+
+/// Checks that a latitude is inside [-90, 90] and that a longitude is finite.
+///
+/// Non-finite values are reported as `InvalidInput`, like [`require_finite`] does. Any finite
+/// longitude is accepted since it names the same meridian as its [`normalize_longitude_rust`]
+/// counterpart.
+fn validate_lat_lon(latitude: f64, longitude: f64) -> Result<(), GeoError> {
+    require_finite(&[("latitude", latitude), ("longitude", longitude)])?;
+    if !(-90.0..=90.0).contains(&latitude) {
+        return Err(GeoError::InvalidLatitude(latitude));
+    }
+    Ok(())
+}
+
+/// Rejects NaN and infinite inputs, which would otherwise propagate silently through the
+/// floating-point arithmetic
+fn require_finite(values: &[(&str, f64)]) -> Result<(), GeoError> {
+    for &(name, value) in values {
+        if !value.is_finite() {
+            return Err(GeoError::InvalidInput(format!(
+                "{} must be a finite number, got {}",
+                name, value
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Reference ellipsoid used by the geodetic conversions.
+///
+/// An ellipsoid is defined by its semi-major axis `a` in meters and its inverse flattening
+/// `1 / f`. The most common datums are available as associated constants.
+///
+/// # Example
+/// ```
+/// use rust::Ellipsoid;
+///
+/// let grs80 = Ellipsoid::GRS80;
+/// assert_eq!(grs80.a, 6378137.0);
+/// assert!((grs80.semi_minor_axis() - 6356752.314140).abs() < 1e-6);
+///
+/// // Custom ellipsoids can be built directly
+/// let sphere_like = Ellipsoid { a: 6371000.0, inv_flattening: f64::INFINITY };
+/// assert_eq!(sphere_like.flattening(), 0.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ellipsoid {
+    /// Semi-major axis in meters.
+    pub a: f64,
+    /// Inverse flattening, `1 / f`.
+    pub inv_flattening: f64,
+}
+
+impl Ellipsoid {
+    /// World Geodetic System 1984, used by GPS.
+    pub const WGS84: Ellipsoid = Ellipsoid {
+        a: 6378137.0,
+        inv_flattening: 298.257223563,
+    };
+    /// Geodetic Reference System 1980, used by NAD83 and ETRS89.
+    pub const GRS80: Ellipsoid = Ellipsoid {
+        a: 6378137.0,
+        inv_flattening: 298.257222101,
+    };
+    /// Clarke 1866, used by NAD27.
+    pub const CLARKE_1866: Ellipsoid = Ellipsoid {
+        a: 6378206.4,
+        inv_flattening: 294.978698214,
+    };
+
+    /// Flattening `f`.
+    pub fn flattening(&self) -> f64 {
+        1.0 / self.inv_flattening
+    }
+
+    /// Semi-minor axis `b` in meters.
+    pub fn semi_minor_axis(&self) -> f64 {
+        self.a * (1.0 - self.flattening())
+    }
+
+    /// Square of the first eccentricity, `2f - f^2`.
+    pub fn eccentricity_squared(&self) -> f64 {
+        let f = self.flattening();
+        2.0 * f - f * f
+    }
+}
+
+/// Converts latitude and longitude on the WGS84 ellipsoid to Cartesian XYZ coordinates.
+///
+/// # Parameters
+/// - `latitude`: Latitude in degrees. Positive values indicate north of the equator, and negative values indicate south.
+/// - `longitude`: Longitude in degrees. Positive values indicate east of the Prime Meridian, and negative values indicate west.
+/// - `height`: Height above the WGS84 ellipsoid in meters. This is the elevation from the ellipsoid surface.
+///
+/// # Returns
+/// A `Result` containing the tuple `(X, Y, Z)` of Cartesian coordinates in meters, or
+/// `GeoError::InvalidInput` if any input is NaN or infinite.
+///
+/// # WGS84 Ellipsoid Constants
+/// - `a`: Semi-major axis, 6378137.0 meters.
+/// - `f`: Flattening factor, 1 / 298.257223563.
+/// - `e2`: Square of eccentricity, calculated as `2 * f - f * f`.
+///
+/// Use [`lat_lon_to_xyz_on`] for other reference ellipsoids.
+///
+/// # Example
+/// ```
+/// use rust::lat_lon_to_xyz_rust;
+/// // Point on the equator at sea level
+/// let (x, y, z) = lat_lon_to_xyz_rust(0.0, 0.0, 0.0).unwrap();
+/// assert!((x - 6378137.0).abs() < 1e-6);
+/// assert!(y.abs() < 1e-6);
+/// assert!(z.abs() < 1e-6);
+///
+/// // Point on the equator at the Prime Meridian with 1000 meters elevation
+/// let (x, y, z) = lat_lon_to_xyz_rust(0.0, 0.0, 1000.0).unwrap();
+/// assert!((x - 6378137.0 - 1000.0).abs() < 1e-6);
+/// assert!(y.abs() < 1e-6);
+/// assert!(z.abs() < 1e-6);
+///
+/// // Point on the equator at 90 degrees East
+/// let (x, y, z) = lat_lon_to_xyz_rust(0.0, 90.0, 0.0).unwrap();
+/// assert!(x.abs() < 1e-6);
+/// assert!((y - 6378137.0).abs() < 1e-6);
+/// assert!(z.abs() < 1e-6);
+///
+/// // Away from the equator the result depends on the flattening
+/// let (x, y, z) = lat_lon_to_xyz_rust(45.0, 0.0, 0.0).unwrap();
+/// assert!((x - 4517590.878849).abs() < 1e-6);
+/// assert!(y.abs() < 1e-6);
+/// assert!((z - 4487348.408866).abs() < 1e-6);
+///
+/// // Error case: NaN input
+/// assert!(lat_lon_to_xyz_rust(f64::NAN, 0.0, 0.0).is_err());
+/// ```
+pub fn lat_lon_to_xyz_rust(
+    latitude: f64,
+    longitude: f64,
+    height: f64,
+) -> Result<(f64, f64, f64), GeoError> {
+    // Ellipsoidal heights need no geoid correction
+    lat_lon_to_xyz_with_geoid_rust(latitude, longitude, height, 0.0)
+}
+
+/// Converts latitude, longitude and an orthometric (mean sea level) height to WGS84 Cartesian
+/// XYZ coordinates.
+///
+/// The ellipsoidal height is found as `h = H + N` from the orthometric height `H` and the
+/// geoid separation `N`, the height of the geoid above the ellipsoid at the point. No geoid
+/// model is built in: look `N` up in a model such as EGM2008 and pass it in. With a separation
+/// of 0 this is the same as [`lat_lon_to_xyz_rust`].
+///
+/// # Returns
+/// A `Result` containing the tuple `(X, Y, Z)` in meters, or `GeoError::InvalidInput` if any
+/// input is NaN or infinite.
+///
+/// # Example
+/// ```
+/// use rust::{lat_lon_to_xyz_rust, lat_lon_to_xyz_with_geoid_rust};
+///
+/// // The geoid is about 47 m above the ellipsoid in Paris
+/// let (lat, lon) = (48.8566_f64, 2.3522_f64);
+/// let (x0, y0, z0) = lat_lon_to_xyz_rust(lat, lon, 35.0).unwrap();
+/// let (x, y, z) = lat_lon_to_xyz_with_geoid_rust(lat, lon, 35.0, 47.3).unwrap();
+///
+/// // The point moves exactly 47.3 m along the ellipsoid normal
+/// let (sin_lat, cos_lat) = lat.to_radians().sin_cos();
+/// let (sin_lon, cos_lon) = lon.to_radians().sin_cos();
+/// let normal = [cos_lat * cos_lon, cos_lat * sin_lon, sin_lat];
+/// let shift = [x - x0, y - y0, z - z0];
+/// for i in 0..3 {
+///     assert!((shift[i] - 47.3 * normal[i]).abs() < 1e-6);
+/// }
+///
+/// // Zero separation matches the ellipsoidal height conversion
+/// assert_eq!(
+///     lat_lon_to_xyz_with_geoid_rust(lat, lon, 35.0, 0.0),
+///     lat_lon_to_xyz_rust(lat, lon, 35.0)
+/// );
+///
+/// // Error case: Geoid separation is NaN
+/// assert!(lat_lon_to_xyz_with_geoid_rust(lat, lon, 35.0, f64::NAN).is_err());
+/// ```
+pub fn lat_lon_to_xyz_with_geoid_rust(
+    latitude: f64,
+    longitude: f64,
+    orthometric_height: f64,
+    geoid_separation: f64,
+) -> Result<(f64, f64, f64), GeoError> {
+    require_finite(&[
+        ("orthometric height", orthometric_height),
+        ("geoid separation", geoid_separation),
+    ])?;
+    lat_lon_to_xyz_on(
+        latitude,
+        longitude,
+        orthometric_height + geoid_separation,
+        &Ellipsoid::WGS84,
+    )
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn lat_lon_to_xyz_with_geoid(
+    latitude: f64,
+    longitude: f64,
+    orthometric_height: f64,
+    geoid_separation: f64,
+) -> Result<Vec<f64>, JsValue> {
+    match lat_lon_to_xyz_with_geoid_rust(latitude, longitude, orthometric_height, geoid_separation)
+    {
+        Ok((x, y, z)) => Ok(vec![x, y, z]),
+        Err(err) => Err(JsValue::from_str(&err.to_string())),
+    }
+}
+
+/// Converts latitude and longitude on an arbitrary reference ellipsoid to Cartesian XYZ
+/// coordinates.
+///
+/// Same as [`lat_lon_to_xyz_rust`] but with the ellipsoid supplied by the caller, and with
+/// `height` measured above that ellipsoid.
+///
+/// # Example
+/// ```
+/// use rust::{lat_lon_to_xyz_on, Ellipsoid};
+///
+/// // On the equator X is simply the semi-major axis of the chosen ellipsoid
+/// let (x, _, _) = lat_lon_to_xyz_on(0.0, 0.0, 0.0, &Ellipsoid::CLARKE_1866).unwrap();
+/// assert!((x - 6378206.4).abs() < 1e-6);
+///
+/// // At the pole Z is the semi-minor axis
+/// let (_, _, z) = lat_lon_to_xyz_on(90.0, 0.0, 0.0, &Ellipsoid::GRS80).unwrap();
+/// assert!((z - Ellipsoid::GRS80.semi_minor_axis()).abs() < 1e-6);
+/// ```
+pub fn lat_lon_to_xyz_on(
+    latitude: f64,
+    longitude: f64,
+    height: f64,
+    ellipsoid: &Ellipsoid,
+) -> Result<(f64, f64, f64), GeoError> {
+    require_finite(&[
+        ("latitude", latitude),
+        ("longitude", longitude),
+        ("height", height),
+        ("semi-major axis", ellipsoid.a),
+        ("inverse flattening", ellipsoid.inv_flattening),
+    ])?;
+
+    let a = ellipsoid.a;
+    let e2 = ellipsoid.eccentricity_squared();
+
+    // Convert latitude and longitude from degrees to radians
+    let lat_rad = latitude * PI / 180.0;
+    let lon_rad = longitude * PI / 180.0;
+
+    // Calculate the prime vertical radius of curvature
+    let n = a / (1.0 - e2 * lat_rad.sin().powi(2)).sqrt();
+
+    // Calculate X, Y, and Z coordinates
+    let x = (n + height) * lat_rad.cos() * lon_rad.cos();
+    let y = (n + height) * lat_rad.cos() * lon_rad.sin();
+    let z = (n * (1.0 - e2) + height) * lat_rad.sin();
+
+    Ok((x, y, z))
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn lat_lon_to_xyz(latitude: f64, longitude: f64, height: f64) -> Result<Vec<f64>, JsValue> {
+    match lat_lon_to_xyz_rust(latitude, longitude, height) {
+        Ok((x, y, z)) => Ok(vec![x, y, z]),
+        Err(err) => Err(JsValue::from_str(&err.to_string())),
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn lat_lon_to_xyz_on_ellipsoid(
+    latitude: f64,
+    longitude: f64,
+    height: f64,
+    semi_major_axis: f64,
+    inv_flattening: f64,
+) -> Result<Vec<f64>, JsValue> {
+    let ellipsoid = Ellipsoid {
+        a: semi_major_axis,
+        inv_flattening,
+    };
+    match lat_lon_to_xyz_on(latitude, longitude, height, &ellipsoid) {
+        Ok((x, y, z)) => Ok(vec![x, y, z]),
+        Err(err) => Err(JsValue::from_str(&err.to_string())),
+    }
+}
+
+/// Converts Cartesian XYZ coordinates to latitude, longitude and height on the WGS84 ellipsoid.
+///
+/// This is the inverse of [`lat_lon_to_xyz_rust`]. Latitude is found with Bowring's iterative
+/// method, which converges to well below a micrometer in two or three iterations for any point
+/// near the Earth's surface.
+///
+/// # Parameters
+/// - `x`, `y`, `z`: Cartesian coordinates in meters.
+///
+/// # Returns
+/// A `Result` containing the tuple `(latitude, longitude, height)` with angles in degrees and
+/// height in meters above the WGS84 ellipsoid, or `GeoError::InvalidInput` if any coordinate
+/// is NaN or infinite.
+///
+/// # Poles
+/// On the polar axis (`x == 0` and `y == 0`) longitude is undefined. By convention this
+/// function returns a longitude of `0.0` there, and a latitude of `90.0` or `-90.0` depending
+/// on the sign of `z`.
+///
+/// # Example
+/// ```
+/// use rust::{lat_lon_to_xyz_rust, xyz_to_lat_lon_rust};
+///
+/// // Point on the equator at sea level
+/// let (lat, lon, h) = xyz_to_lat_lon_rust(6378137.0, 0.0, 0.0).unwrap();
+/// assert!(lat.abs() < 1e-9);
+/// assert!(lon.abs() < 1e-9);
+/// assert!(h.abs() < 1e-6);
+///
+/// // North pole: longitude is reported as 0.0
+/// let (x, y, z) = lat_lon_to_xyz_rust(90.0, 0.0, 0.0).unwrap();
+/// let (lat, lon, h) = xyz_to_lat_lon_rust(0.0, 0.0, z).unwrap();
+/// assert_eq!(lat, 90.0);
+/// assert_eq!(lon, 0.0);
+/// assert!(h.abs() < 1e-6);
+/// assert!(x.abs() < 1e-6 && y.abs() < 1e-6);
+///
+/// // South pole 500 meters above the ellipsoid
+/// let (_, _, z) = lat_lon_to_xyz_rust(-90.0, 0.0, 500.0).unwrap();
+/// let (lat, lon, h) = xyz_to_lat_lon_rust(0.0, 0.0, z).unwrap();
+/// assert_eq!(lat, -90.0);
+/// assert_eq!(lon, 0.0);
+/// assert!((h - 500.0).abs() < 1e-6);
+///
+/// // Round trip of a point with nonzero height
+/// let (x, y, z) = lat_lon_to_xyz_rust(48.8566, 2.3522, 1234.5).unwrap();
+/// let (lat, lon, h) = xyz_to_lat_lon_rust(x, y, z).unwrap();
+/// assert!((lat - 48.8566).abs() < 1e-6);
+/// assert!((lon - 2.3522).abs() < 1e-6);
+/// assert!((h - 1234.5).abs() < 1e-3);
+///
+/// // Round trip in the southern and western hemispheres
+/// let (x, y, z) = lat_lon_to_xyz_rust(-33.8688, -151.2093, -50.0).unwrap();
+/// let (lat, lon, h) = xyz_to_lat_lon_rust(x, y, z).unwrap();
+/// assert!((lat + 33.8688).abs() < 1e-6);
+/// assert!((lon + 151.2093).abs() < 1e-6);
+/// assert!((h + 50.0).abs() < 1e-3);
+/// ```
+pub fn xyz_to_lat_lon_rust(x: f64, y: f64, z: f64) -> Result<(f64, f64, f64), GeoError> {
+    xyz_to_lat_lon_on(x, y, z, &Ellipsoid::WGS84)
+}
+
+/// Converts Cartesian XYZ coordinates to latitude, longitude and height on an arbitrary
+/// reference ellipsoid.
+///
+/// Same as [`xyz_to_lat_lon_rust`] but with the ellipsoid supplied by the caller.
+///
+/// # Example
+/// ```
+/// use rust::{lat_lon_to_xyz_on, xyz_to_lat_lon_on, Ellipsoid};
+///
+/// let clarke = Ellipsoid::CLARKE_1866;
+/// let (x, y, z) = lat_lon_to_xyz_on(38.0, -97.0, 350.0, &clarke).unwrap();
+/// let (lat, lon, h) = xyz_to_lat_lon_on(x, y, z, &clarke).unwrap();
+/// assert!((lat - 38.0).abs() < 1e-6);
+/// assert!((lon + 97.0).abs() < 1e-6);
+/// assert!((h - 350.0).abs() < 1e-3);
+/// ```
+pub fn xyz_to_lat_lon_on(
+    x: f64,
+    y: f64,
+    z: f64,
+    ellipsoid: &Ellipsoid,
+) -> Result<(f64, f64, f64), GeoError> {
+    require_finite(&[
+        ("x", x),
+        ("y", y),
+        ("z", z),
+        ("semi-major axis", ellipsoid.a),
+        ("inverse flattening", ellipsoid.inv_flattening),
+    ])?;
+
+    let a = ellipsoid.a;
+    let f = ellipsoid.flattening();
+    let b = ellipsoid.semi_minor_axis();
+    let e2 = ellipsoid.eccentricity_squared();
+    let ep2 = e2 / (1.0 - e2); // Square of second eccentricity
+
+    // Distance from the polar axis
+    let p = x.hypot(y);
+
+    if p == 0.0 {
+        // On the polar axis longitude is undefined, report 0.0
+        let latitude = if z >= 0.0 { 90.0 } else { -90.0 };
+        return Ok((latitude, 0.0, z.abs() - b));
+    }
+
+    let longitude = y.atan2(x);
+
+    // Bowring's iteration on the parametric (reduced) latitude
+    let mut beta = (a * z).atan2(b * p);
+    let mut lat_rad = 0.0;
+    for _ in 0..10 {
+        lat_rad = (z + ep2 * b * beta.sin().powi(3)).atan2(p - e2 * a * beta.cos().powi(3));
+        let next_beta = ((1.0 - f) * lat_rad.sin()).atan2(lat_rad.cos());
+        if (next_beta - beta).abs() < 1e-15 {
+            break;
+        }
+        beta = next_beta;
+    }
+
+    // Height above the ellipsoid, valid at any latitude
+    let height =
+        p * lat_rad.cos() + z * lat_rad.sin() - a * (1.0 - e2 * lat_rad.sin().powi(2)).sqrt();
+
+    Ok((lat_rad * 180.0 / PI, longitude * 180.0 / PI, height))
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn xyz_to_lat_lon(x: f64, y: f64, z: f64) -> Result<Vec<f64>, JsValue> {
+    match xyz_to_lat_lon_rust(x, y, z) {
+        Ok((latitude, longitude, height)) => Ok(vec![latitude, longitude, height]),
+        Err(err) => Err(JsValue::from_str(&err.to_string())),
+    }
+}
+
+/// Converts Cartesian ECEF coordinates to a local East-North-Up (ENU) frame.
+///
+/// The ENU frame is the tangent plane to the WGS84 ellipsoid at the reference point, with the
+/// Up axis along the ellipsoid normal.
+///
+/// # Parameters
+/// - `x`, `y`, `z`: ECEF coordinates of the point in meters.
+/// - `ref_lat`, `ref_lon`: Latitude and longitude of the reference point in degrees.
+/// - `ref_height`: Height of the reference point above the WGS84 ellipsoid in meters.
+///
+/// # Returns
+/// A `Result` containing the tuple `(east, north, up)` in meters relative to the reference
+/// point, or `GeoError::InvalidInput` if any input is NaN or infinite.
+///
+/// # Example
+/// ```
+/// use rust::{ecef_to_enu_rust, lat_lon_to_xyz_rust};
+///
+/// // A point 250 meters straight above the reference
+/// let (x, y, z) = lat_lon_to_xyz_rust(45.0, 7.0, 350.0).unwrap();
+/// let (e, n, u) = ecef_to_enu_rust(x, y, z, 45.0, 7.0, 100.0).unwrap();
+/// assert!(e.abs() < 1e-6);
+/// assert!(n.abs() < 1e-6);
+/// assert!((u - 250.0).abs() < 1e-6);
+///
+/// // A point slightly to the east on the equator
+/// let (x, y, z) = lat_lon_to_xyz_rust(0.0, 0.001, 0.0).unwrap();
+/// let (e, n, _) = ecef_to_enu_rust(x, y, z, 0.0, 0.0, 0.0).unwrap();
+/// assert!((e - 111.319).abs() < 1e-3);
+/// assert!(n.abs() < 1e-6);
+/// ```
+pub fn ecef_to_enu_rust(
+    x: f64,
+    y: f64,
+    z: f64,
+    ref_lat: f64,
+    ref_lon: f64,
+    ref_height: f64,
+) -> Result<(f64, f64, f64), GeoError> {
+    require_finite(&[("x", x), ("y", y), ("z", z)])?;
+    let (x0, y0, z0) = lat_lon_to_xyz_rust(ref_lat, ref_lon, ref_height)?;
+    let (dx, dy, dz) = (x - x0, y - y0, z - z0);
+
+    let (sin_lat, cos_lat) = (ref_lat * PI / 180.0).sin_cos();
+    let (sin_lon, cos_lon) = (ref_lon * PI / 180.0).sin_cos();
+
+    let east = -sin_lon * dx + cos_lon * dy;
+    let north = -sin_lat * cos_lon * dx - sin_lat * sin_lon * dy + cos_lat * dz;
+    let up = cos_lat * cos_lon * dx + cos_lat * sin_lon * dy + sin_lat * dz;
+
+    Ok((east, north, up))
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn ecef_to_enu(
+    x: f64,
+    y: f64,
+    z: f64,
+    ref_lat: f64,
+    ref_lon: f64,
+    ref_height: f64,
+) -> Result<Vec<f64>, JsValue> {
+    match ecef_to_enu_rust(x, y, z, ref_lat, ref_lon, ref_height) {
+        Ok((east, north, up)) => Ok(vec![east, north, up]),
+        Err(err) => Err(JsValue::from_str(&err.to_string())),
+    }
+}
+
+/// Converts local East-North-Up (ENU) coordinates back to Cartesian ECEF coordinates.
+///
+/// This is the inverse of [`ecef_to_enu_rust`] for the same reference point.
+///
+/// # Parameters
+/// - `east`, `north`, `up`: Coordinates in meters in the local frame.
+/// - `ref_lat`, `ref_lon`: Latitude and longitude of the reference point in degrees.
+/// - `ref_height`: Height of the reference point above the WGS84 ellipsoid in meters.
+///
+/// # Returns
+/// A `Result` containing the tuple `(X, Y, Z)` of ECEF coordinates in meters, or
+/// `GeoError::InvalidInput` if any input is NaN or infinite.
+///
+/// # Example
+/// ```
+/// use rust::{ecef_to_enu_rust, enu_to_ecef_rust, lat_lon_to_xyz_rust};
+///
+/// // Round trip through the local frame
+/// let (x, y, z) = lat_lon_to_xyz_rust(-33.9, 18.4, 20.0).unwrap();
+/// let (e, n, u) = ecef_to_enu_rust(x, y, z, -34.0, 18.5, 0.0).unwrap();
+/// let (x2, y2, z2) = enu_to_ecef_rust(e, n, u, -34.0, 18.5, 0.0).unwrap();
+/// assert!((x - x2).abs() < 1e-6);
+/// assert!((y - y2).abs() < 1e-6);
+/// assert!((z - z2).abs() < 1e-6);
+///
+/// // The origin of the local frame is the reference point itself
+/// let (x, y, z) = enu_to_ecef_rust(0.0, 0.0, 0.0, 10.0, 20.0, 30.0).unwrap();
+/// let (x0, y0, z0) = lat_lon_to_xyz_rust(10.0, 20.0, 30.0).unwrap();
+/// assert!((x - x0).abs() < 1e-6 && (y - y0).abs() < 1e-6 && (z - z0).abs() < 1e-6);
+/// ```
+pub fn enu_to_ecef_rust(
+    east: f64,
+    north: f64,
+    up: f64,
+    ref_lat: f64,
+    ref_lon: f64,
+    ref_height: f64,
+) -> Result<(f64, f64, f64), GeoError> {
+    require_finite(&[("east", east), ("north", north), ("up", up)])?;
+    let (x0, y0, z0) = lat_lon_to_xyz_rust(ref_lat, ref_lon, ref_height)?;
+
+    let (sin_lat, cos_lat) = (ref_lat * PI / 180.0).sin_cos();
+    let (sin_lon, cos_lon) = (ref_lon * PI / 180.0).sin_cos();
+
+    // The ENU rotation is orthogonal, so its inverse is the transpose
+    let dx = -sin_lon * east - sin_lat * cos_lon * north + cos_lat * cos_lon * up;
+    let dy = cos_lon * east - sin_lat * sin_lon * north + cos_lat * sin_lon * up;
+    let dz = cos_lat * north + sin_lat * up;
+
+    Ok((x0 + dx, y0 + dy, z0 + dz))
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn enu_to_ecef(
+    east: f64,
+    north: f64,
+    up: f64,
+    ref_lat: f64,
+    ref_lon: f64,
+    ref_height: f64,
+) -> Result<Vec<f64>, JsValue> {
+    match enu_to_ecef_rust(east, north, up, ref_lat, ref_lon, ref_height) {
+        Ok((x, y, z)) => Ok(vec![x, y, z]),
+        Err(err) => Err(JsValue::from_str(&err.to_string())),
+    }
+}
+
+//
+// Find best UTM zone for a position
+//
+
+/// Wraps a longitude in degrees into the range [-180, 180).
+///
+/// Longitudes from sources that use [0, 360) or that have drifted past the antimeridian are
+/// mapped onto the same meridian, so 181 becomes -179 and 180 becomes -180. Latitude has no
+/// such equivalent and is always validated strictly.
+///
+/// # Returns
+/// - A `Result` containing the wrapped longitude, or `GeoError::InvalidInput` if the
+///   longitude is NaN or infinite.
+///
+/// # Examples
+/// ```
+/// use rust::{normalize_longitude_rust, GeoError};
+///
+/// assert_eq!(normalize_longitude_rust(190.0), Ok(-170.0));
+/// assert_eq!(normalize_longitude_rust(360.0), Ok(0.0));
+/// assert_eq!(normalize_longitude_rust(-181.0), Ok(179.0));
+/// assert_eq!(normalize_longitude_rust(180.0), Ok(-180.0));
+/// assert_eq!(normalize_longitude_rust(-75.5), Ok(-75.5));
+/// assert!(matches!(
+///     normalize_longitude_rust(f64::INFINITY),
+///     Err(GeoError::InvalidInput(_))
+/// ));
+/// ```
+pub fn normalize_longitude_rust(longitude: f64) -> Result<f64, GeoError> {
+    require_finite(&[("longitude", longitude)])?;
+    Ok(wrap_longitude(longitude))
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn normalize_longitude(longitude: f64) -> Result<f64, JsValue> {
+    normalize_longitude_rust(longitude).map_err(|err| JsValue::from_str(&err.to_string()))
+}
+
+/// Wraps a finite longitude into [-180, 180), as [`normalize_longitude_rust`] does once the
+/// input has been validated
+fn wrap_longitude(longitude: f64) -> f64 {
+    let normalized = (longitude + 180.0).rem_euclid(360.0) - 180.0;
+    // rem_euclid can round up to exactly 360 for inputs just below a multiple of it
+    if normalized >= 180.0 {
+        -180.0
+    } else {
+        normalized
+    }
+}
+
+/// Returns the MGRS latitude band letter for a given latitude
+fn get_mgrs_latitude_band(latitude: f64) -> Result<char, UTMZoneError> {
+    if !(-80.0..84.0).contains(&latitude) {
+        return Err(UTMZoneError::InvalidLatitude(latitude));
+    }
+
+    // The northernmost band 'X' spans 12 degrees instead of 8
+    if latitude >= 72.0 {
+        return Ok('X');
+    }
+
+    let bands: Vec<char> = ('C'..='X').filter(|&c| c != 'I' && c != 'O').collect();
+
+    let index = ((latitude + 80.0) / 8.0).floor() as usize;
+    Ok(bands[index])
+}
+
+/// Calculates the UTM zone number and MGRS latitude band for a given latitude and longitude.
+///
+/// The function handles special UTM zone cases, such as areas in Norway and Svalbard, where
+/// UTM zones deviate from the regular 6-degree longitudinal spacing. It also incorporates
+/// the MGRS latitude bands, which range from 'C' to 'X' (excluding 'I' and 'O').
+///
+/// Longitudes outside [-180, 180) are first wrapped with [`normalize_longitude_rust`], while
+/// latitudes must lie between -90 and 90 degrees.
+///
+/// # Returns
+/// - A `Result` containing the UTM zone number and MGRS latitude band, or an error if the
+///   latitude is outside the valid range or the longitude is not finite.
+///
+/// # Examples
+/// ```
+/// use rust::calculate_utm_zone;
+///
+/// // General case in the Northern Hemisphere
+/// let (zone, band) = calculate_utm_zone(40.0, -75.0).unwrap();
+/// assert_eq!(zone, 18);
+/// assert_eq!(band, 'T');
+///
+/// // General case in the Southern Hemisphere
+/// let (zone, band) = calculate_utm_zone(-33.0, 151.0).unwrap();
+/// assert_eq!(zone, 56);
+/// assert_eq!(band, 'H');
+///
+/// // Special case in Norway
+/// let (zone, band) = calculate_utm_zone(60.0, 5.0).unwrap();
+/// assert_eq!(zone, 32);
+/// assert_eq!(band, 'V');
+///
+/// // Special case in Svalbard
+/// let (zone, band) = calculate_utm_zone(72.0, 7.0).unwrap();
+/// assert_eq!(zone, 31);
+/// assert_eq!(band, 'X');
+///
+/// // Near the equator
+/// let (zone, band) = calculate_utm_zone(57.0, 1.0).unwrap();
+/// assert_eq!(zone, 31);
+/// assert_eq!(band, 'V');
+///
+/// // Another weird case between UK and Norway
+/// let (zone, band) = calculate_utm_zone(0.0, 33.0).unwrap();
+/// assert_eq!(zone, 36);
+/// assert_eq!(band, 'N');
+///
+/// // The 'X' band spans the 12 degrees from 72 to 84
+/// assert_eq!(calculate_utm_zone(72.0, 0.0).unwrap().1, 'X');
+/// assert_eq!(calculate_utm_zone(80.0, 0.0).unwrap().1, 'X');
+/// assert_eq!(calculate_utm_zone(83.9, 0.0).unwrap().1, 'X');
+/// assert_eq!(calculate_utm_zone(71.9, 0.0).unwrap().1, 'W');
+///
+/// // Edge case at the boundary of valid latitude for MGRS
+/// assert!(calculate_utm_zone(84.0, 15.0).is_err());
+///
+/// // Error case: Latitude out of range
+/// assert!(calculate_utm_zone(90.1, 0.0).is_err());
+///
+/// // Longitudes wrap around the antimeridian
+/// assert_eq!(calculate_utm_zone(0.0, 181.0).unwrap(), (1, 'N'));
+/// assert_eq!(calculate_utm_zone(40.0, 285.0).unwrap(), (18, 'T'));
+///
+/// // Error case: Longitude is not a number
+/// assert!(calculate_utm_zone(0.0, f64::NAN).is_err());
+/// ```
+pub fn calculate_utm_zone(latitude: f64, longitude: f64) -> Result<(u32, char), UTMZoneError> {
+    calculate_utm_zone_with_options(latitude, longitude, &UtmOptions::default())
+}
+
+/// Options for the UTM zone and projection calculations.
+///
+/// The [`Default`] options are the ones used by [`calculate_utm_zone`] and
+/// [`lat_lon_to_utm_rust`] and follow the UTM standard.
+///
+/// ```
+/// use rust::UtmOptions;
+///
+/// let strict = UtmOptions {
+///     apply_special_zones: false,
+///     ..UtmOptions::default()
+/// };
+/// assert!(strict.normalize_longitude);
+/// assert_eq!(strict.scale_factor, 0.9996);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UtmOptions {
+    /// Use the widened zone 32V in Norway and the zones 31X to 37X in Svalbard. When `false`
+    /// every zone is exactly 6° wide.
+    pub apply_special_zones: bool,
+    /// Wrap longitudes outside [-180, 180] into range. When `false` such longitudes are
+    /// rejected with `InvalidLongitude`.
+    pub normalize_longitude: bool,
+    /// Scale factor on the central meridian, used by the projection.
+    pub scale_factor: f64,
+}
+
+impl Default for UtmOptions {
+    fn default() -> Self {
+        UtmOptions {
+            apply_special_zones: true,
+            normalize_longitude: true,
+            scale_factor: UTM_SCALE_FACTOR,
+        }
+    }
+}
+
+/// Validates a position against the options and returns the longitude to use
+fn utm_longitude(latitude: f64, longitude: f64, options: &UtmOptions) -> Result<f64, UTMZoneError> {
+    validate_lat_lon(latitude, longitude)?;
+    if options.normalize_longitude {
+        Ok(wrap_longitude(longitude))
+    } else if (-180.0..=180.0).contains(&longitude) {
+        Ok(longitude)
+    } else {
+        Err(UTMZoneError::InvalidLongitude(longitude))
+    }
+}
+
+/// Calculates the UTM zone number and MGRS latitude band like [`calculate_utm_zone`], with
+/// control over the Norway and Svalbard exceptions and over longitude wrapping.
+///
+/// # Examples
+/// ```
+/// use rust::{calculate_utm_zone_with_options, UtmOptions, UTMZoneError};
+///
+/// // Strict 6° zones ignore the Norway exception
+/// let strict = UtmOptions {
+///     apply_special_zones: false,
+///     ..UtmOptions::default()
+/// };
+/// assert_eq!(calculate_utm_zone_with_options(60.0, 5.0, &strict), Ok((31, 'V')));
+/// assert_eq!(calculate_utm_zone_with_options(72.0, 7.0, &strict), Ok((32, 'X')));
+///
+/// // The defaults match calculate_utm_zone
+/// let defaults = UtmOptions::default();
+/// assert_eq!(calculate_utm_zone_with_options(60.0, 5.0, &defaults), Ok((32, 'V')));
+///
+/// // Without normalization longitudes must already be in range
+/// let no_wrap = UtmOptions {
+///     normalize_longitude: false,
+///     ..UtmOptions::default()
+/// };
+/// assert_eq!(
+///     calculate_utm_zone_with_options(0.0, 181.0, &no_wrap),
+///     Err(UTMZoneError::InvalidLongitude(181.0))
+/// );
+/// assert_eq!(calculate_utm_zone_with_options(0.0, 180.0, &no_wrap), Ok((1, 'N')));
+/// ```
+pub fn calculate_utm_zone_with_options(
+    latitude: f64,
+    longitude: f64,
+    options: &UtmOptions,
+) -> Result<(u32, char), UTMZoneError> {
+    let longitude = utm_longitude(latitude, longitude, options)?;
+
+    let zone_number = if !options.apply_special_zones {
+        ((longitude + 180.0) / 6.0).floor() as u32 % 60 + 1
+    } else if latitude > 55.0 && latitude < 64.0 && longitude > 2.0 && longitude < 6.0 {
+        32
+    } else if latitude > 71.0 && (6.0..9.0).contains(&longitude) {
+        31
+    } else if latitude > 71.0
+        && ((9.0..12.0).contains(&longitude) || (18.0..21.0).contains(&longitude))
+    {
+        33
+    } else if latitude > 71.0
+        && ((21.0..24.0).contains(&longitude) || (30.0..33.0).contains(&longitude))
+    {
+        35
+    } else {
+        ((longitude + 180.0) / 6.0).floor() as u32 % 60 + 1
+    };
+
+    let latitude_band = get_mgrs_latitude_band(latitude)?;
+
+    Ok((zone_number, latitude_band))
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn get_utm_zone_from_lat_lon(latitude: f64, longitude: f64) -> Result<JsValue, JsValue> {
+    match calculate_utm_zone(latitude, longitude) {
+        Ok((zone_number, latitude_band)) => {
+            let result = format!("{}{}", zone_number, latitude_band);
+            Ok(JsValue::from_str(&result))
+        }
+        Err(err) => Err(JsValue::from_str(&err.to_string())),
+    }
+}
+
+/// Returns whether a latitude belongs to the northern hemisphere for UTM purposes.
+///
+/// Following the UTM convention the equator itself is northern, so southern hemisphere false
+/// northings only apply to strictly negative latitudes.
+///
+/// # Examples
+/// ```
+/// use rust::is_northern_hemisphere_rust;
+///
+/// assert!(is_northern_hemisphere_rust(45.0));
+/// assert!(is_northern_hemisphere_rust(0.0));
+/// assert!(!is_northern_hemisphere_rust(-0.1));
+/// ```
+pub fn is_northern_hemisphere_rust(latitude: f64) -> bool {
+    latitude >= 0.0
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn is_northern_hemisphere(latitude: f64) -> bool {
+    is_northern_hemisphere_rust(latitude)
+}
+
+/// Returns whether an MGRS latitude band lies in the northern hemisphere.
+///
+/// Bands 'C' to 'M' are southern and bands 'N' to 'X' are northern; band 'N' starts at the
+/// equator.
+///
+/// # Returns
+/// - A `Result` containing `true` for northern bands, or `UTMZoneError::InvalidBand` if the
+///   letter is not a latitude band. Lowercase letters are accepted.
+///
+/// # Examples
+/// ```
+/// use rust::is_northern_band_rust;
+///
+/// assert!(!is_northern_band_rust('M').unwrap());
+/// assert!(is_northern_band_rust('N').unwrap());
+/// assert!(is_northern_band_rust('x').unwrap());
+///
+/// // Error cases: 'I' and 'O' are never used, and 'A' is outside the UTM bands
+/// assert!(is_northern_band_rust('I').is_err());
+/// assert!(is_northern_band_rust('A').is_err());
+/// ```
+pub fn is_northern_band_rust(band: char) -> Result<bool, UTMZoneError> {
+    let upper = band.to_ascii_uppercase();
+    if !('C'..='X').contains(&upper) || upper == 'I' || upper == 'O' {
+        return Err(UTMZoneError::InvalidBand(band));
+    }
+    Ok(upper >= 'N')
+}
+
+/// Calculates the UTM zone number, MGRS latitude band and hemisphere for a given latitude and
+/// longitude.
+///
+/// Same as [`calculate_utm_zone`] with an extra flag that is `true` in the northern
+/// hemisphere, including the equator.
+///
+/// # Examples
+/// ```
+/// use rust::calculate_utm_zone_with_hemisphere;
+///
+/// assert_eq!(calculate_utm_zone_with_hemisphere(-33.0, 151.0).unwrap(), (56, 'H', false));
+/// assert_eq!(calculate_utm_zone_with_hemisphere(40.0, -75.0).unwrap(), (18, 'T', true));
+///
+/// // The equator is in band 'N' and treated as northern
+/// assert_eq!(calculate_utm_zone_with_hemisphere(0.0, 33.0).unwrap(), (36, 'N', true));
+/// ```
+pub fn calculate_utm_zone_with_hemisphere(
+    latitude: f64,
+    longitude: f64,
+) -> Result<(u32, char, bool), UTMZoneError> {
+    let (zone_number, latitude_band) = calculate_utm_zone(latitude, longitude)?;
+    Ok((
+        zone_number,
+        latitude_band,
+        is_northern_hemisphere_rust(latitude),
+    ))
+}
+
+/// Lists every UTM zone and latitude band touched by a latitude/longitude bounding box.
+///
+/// Use this to detect footprints that cross a zone boundary and cannot be projected in a
+/// single zone. The box includes its edges, so a box ending exactly on a zone boundary touches
+/// the next zone too. The Norway and Svalbard exceptions are honoured. Longitudes may extend
+/// past ±180 to describe a box crossing the antimeridian, as long as `min_lon < max_lon`.
+///
+/// # Returns
+/// - A `Result` containing the distinct `(zone, band)` pairs sorted by zone and then band, or
+///   an error if a corner is out of range (including latitudes outside the UTM limits of -80
+///   and 84 degrees) or the minimum is not below the maximum on both axes.
+///
+/// # Examples
+/// ```
+/// use rust::utm_zones_for_bbox_rust;
+///
+/// // Entirely inside zone 31, band U
+/// assert_eq!(utm_zones_for_bbox_rust(48.5, 1.0, 49.5, 2.0).unwrap(), vec![(31, 'U')]);
+///
+/// // Straddling the 6° boundary between zones 31 and 32
+/// assert_eq!(
+///     utm_zones_for_bbox_rust(48.5, 5.5, 49.5, 6.5).unwrap(),
+///     vec![(31, 'U'), (32, 'U')]
+/// );
+///
+/// // South-western Norway, where zone 32V is widened to the west
+/// assert_eq!(
+///     utm_zones_for_bbox_rust(58.0, 4.0, 60.0, 5.0).unwrap(),
+///     vec![(32, 'V')]
+/// );
+///
+/// // Crossing the antimeridian and a band boundary
+/// assert_eq!(
+///     utm_zones_for_bbox_rust(-1.0, 179.0, 1.0, 181.0).unwrap(),
+///     vec![(1, 'M'), (1, 'N'), (60, 'M'), (60, 'N')]
+/// );
+///
+/// // Error cases: inverted box, latitude outside the UTM range
+/// assert!(utm_zones_for_bbox_rust(49.5, 1.0, 48.5, 2.0).is_err());
+/// assert!(utm_zones_for_bbox_rust(48.5, 2.0, 49.5, 1.0).is_err());
+/// assert!(utm_zones_for_bbox_rust(80.0, 0.0, 85.0, 1.0).is_err());
+/// ```
+pub fn utm_zones_for_bbox_rust(
+    min_lat: f64,
+    min_lon: f64,
+    max_lat: f64,
+    max_lon: f64,
+) -> Result<Vec<(u32, char)>, UTMZoneError> {
+    validate_lat_lon(min_lat, min_lon)?;
+    validate_lat_lon(max_lat, max_lon)?;
+    if min_lat >= max_lat || min_lon >= max_lon {
+        return Err(UTMZoneError::InvalidInput(format!(
+            "the bounding box ({}, {}) to ({}, {}) must have min_lat < max_lat and \
+             min_lon < max_lon",
+            min_lat, min_lon, max_lat, max_lon
+        )));
+    }
+    // A box wider than the globe touches the same zones as one exactly as wide
+    let max_lon = max_lon.min(min_lon + 360.0);
+
+    // Zones and bands are constant between these lines: band edges every 8°, the edges of
+    // the Norway and Svalbard exceptions, and meridians every 3°, which covers both the
+    // regular 6° zone edges and the Svalbard ones
+    let band_edges = (0..20).map(|i| -80.0 + 8.0 * i as f64);
+    let special_parallels = [55.0, 56.0, 64.0, 71.0];
+    let latitudes = sample_between(
+        min_lat,
+        max_lat,
+        band_edges.chain(special_parallels).collect(),
+    );
+
+    let first = (min_lon / 3.0).ceil() as i64;
+    let last = (max_lon / 3.0).floor() as i64;
+    let mut meridians: Vec<f64> = (first..=last).map(|k| 3.0 * k as f64).collect();
+    // The western edge of zone 32V, repeated every full turn
+    let turns = ((min_lon - 2.0) / 360.0).ceil() as i64..=((max_lon - 2.0) / 360.0).floor() as i64;
+    meridians.extend(turns.map(|turn| 2.0 + 360.0 * turn as f64));
+    let longitudes = sample_between(min_lon, max_lon, meridians);
+
+    let mut zones = alloc::collections::BTreeSet::new();
+    for &latitude in &latitudes {
+        for &longitude in &longitudes {
+            zones.insert(calculate_utm_zone(latitude, longitude)?);
+        }
+    }
+
+    Ok(zones.into_iter().collect())
+}
+
+/// Returns the ends of `[min, max]`, the interior break points and the midpoints between them,
+/// so that every piece of a piecewise constant function on the interval is sampled
+fn sample_between(min: f64, max: f64, break_points: Vec<f64>) -> Vec<f64> {
+    let mut edges: Vec<f64> = core::iter::once(min)
+        .chain(break_points.into_iter().filter(|&b| b > min && b < max))
+        .chain(core::iter::once(max))
+        .collect();
+    edges.sort_by(f64::total_cmp);
+    edges.dedup();
+
+    let midpoints: Vec<f64> = edges.windows(2).map(|w| (w[0] + w[1]) / 2.0).collect();
+    edges.extend(midpoints);
+    edges
+}
+
+/// Wasm entry point for [`utm_zones_for_bbox_rust`] returning the zones as strings like
+/// `"32U"`.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn utm_zones_for_bbox(
+    min_lat: f64,
+    min_lon: f64,
+    max_lat: f64,
+    max_lon: f64,
+) -> Result<Vec<String>, JsValue> {
+    match utm_zones_for_bbox_rust(min_lat, min_lon, max_lat, max_lon) {
+        Ok(zones) => Ok(zones
+            .into_iter()
+            .map(|(zone, band)| format!("{}{}", zone, band))
+            .collect()),
+        Err(err) => Err(JsValue::from_str(&err.to_string())),
+    }
+}
+
+/// Scale factor on the central meridian of every UTM zone
+const UTM_SCALE_FACTOR: f64 = 0.9996;
+/// False easting added to every UTM easting, in meters
+const UTM_FALSE_EASTING: f64 = 500000.0;
+/// False northing added to southern hemisphere UTM northings, in meters
+const UTM_FALSE_NORTHING_SOUTH: f64 = 10000000.0;
+
+/// Returns the longitude of the central meridian of a UTM zone in degrees
+fn utm_central_meridian(zone: u32) -> f64 {
+    -183.0 + 6.0 * zone as f64
+}
+
+/// Returns the longitudes bounding a UTM zone and its central meridian.
+///
+/// These are the regular 6 degree zone limits. The Norway and Svalbard exceptions, where
+/// zones 31 to 37 are widened or narrowed at high latitudes, are deliberately ignored.
+///
+/// # Returns
+/// - A `Result` containing `(west_longitude, central_meridian, east_longitude)` in degrees, or
+///   `UTMZoneError::InvalidZone` if the zone is not between 1 and 60.
+///
+/// # Examples
+/// ```
+/// use rust::utm_zone_bounds_rust;
+///
+/// assert_eq!(utm_zone_bounds_rust(1).unwrap(), (-180.0, -177.0, -174.0));
+/// assert_eq!(utm_zone_bounds_rust(31).unwrap(), (0.0, 3.0, 6.0));
+/// assert_eq!(utm_zone_bounds_rust(60).unwrap(), (174.0, 177.0, 180.0));
+///
+/// // Error case: Zone out of range
+/// assert!(utm_zone_bounds_rust(0).is_err());
+/// assert!(utm_zone_bounds_rust(61).is_err());
+/// ```
+pub fn utm_zone_bounds_rust(zone: u32) -> Result<(f64, f64, f64), UTMZoneError> {
+    if !(1..=60).contains(&zone) {
+        return Err(UTMZoneError::InvalidZone(zone));
+    }
+
+    let central_meridian = utm_central_meridian(zone);
+    Ok((
+        central_meridian - 3.0,
+        central_meridian,
+        central_meridian + 3.0,
+    ))
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn utm_zone_bounds(zone: u32) -> Result<Vec<f64>, JsValue> {
+    match utm_zone_bounds_rust(zone) {
+        Ok((west, central, east)) => Ok(vec![west, central, east]),
+        Err(err) => Err(JsValue::from_str(&err.to_string())),
+    }
+}
+
+/// Coefficients of the Krüger series for the WGS84 ellipsoid.
+///
+/// Returns the rectifying radius `A` and the `alpha` coefficients of the forward series,
+/// expanded to sixth order in the third flattening `n`.
+fn kruger_coefficients() -> (f64, [f64; 6]) {
+    let ellipsoid = Ellipsoid::WGS84;
+    let f = ellipsoid.flattening();
+    let n = f / (2.0 - f);
+    let n2 = n * n;
+    let n3 = n2 * n;
+    let n4 = n3 * n;
+    let n5 = n4 * n;
+    let n6 = n5 * n;
+
+    let rectifying_radius = ellipsoid.a / (1.0 + n) * (1.0 + n2 / 4.0 + n4 / 64.0 + n6 / 256.0);
+    let alpha = [
+        n / 2.0 - 2.0 * n2 / 3.0 + 5.0 * n3 / 16.0 + 41.0 * n4 / 180.0 - 127.0 * n5 / 288.0
+            + 7891.0 * n6 / 37800.0,
+        13.0 * n2 / 48.0 - 3.0 * n3 / 5.0 + 557.0 * n4 / 1440.0 + 281.0 * n5 / 630.0
+            - 1983433.0 * n6 / 1935360.0,
+        61.0 * n3 / 240.0 - 103.0 * n4 / 140.0 + 15061.0 * n5 / 26880.0 + 167603.0 * n6 / 181440.0,
+        49561.0 * n4 / 161280.0 - 179.0 * n5 / 168.0 + 6601661.0 * n6 / 7257600.0,
+        34729.0 * n5 / 80640.0 - 3418889.0 * n6 / 1995840.0,
+        212378941.0 * n6 / 319334400.0,
+    ];
+
+    (rectifying_radius, alpha)
+}
+
+/// Maps a point to Gauss-Schreiber coordinates on the conformal sphere.
+///
+/// Returns `(tau_prime, xi_prime, eta_prime)`: the tangent of the conformal latitude and the
+/// spherical Transverse Mercator coordinates relative to the central meridian, in radians.
+fn gauss_schreiber(latitude: f64, longitude: f64, central_meridian: f64) -> (f64, f64, f64) {
+    let e = Ellipsoid::WGS84.eccentricity_squared().sqrt();
+
+    let phi = latitude * PI / 180.0;
+    let lambda = (longitude - central_meridian) * PI / 180.0;
+
+    // Conformal latitude, expressed through its tangent
+    let tau = phi.tan();
+    let sigma = (e * (e * tau / (1.0 + tau * tau).sqrt()).atanh()).sinh();
+    let tau_prime = tau * (1.0 + sigma * sigma).sqrt() - sigma * (1.0 + tau * tau).sqrt();
+
+    let xi_prime = tau_prime.atan2(lambda.cos());
+    let eta_prime = (lambda.sin() / (tau_prime * tau_prime + lambda.cos().powi(2)).sqrt()).asinh();
+
+    (tau_prime, xi_prime, eta_prime)
+}
+
+/// Projects a point with the Transverse Mercator projection on the WGS84 ellipsoid.
+///
+/// Returns `(x, y)` in meters relative to the central meridian and the equator, before any
+/// false easting or northing is applied.
+fn transverse_mercator_forward(
+    latitude: f64,
+    longitude: f64,
+    central_meridian: f64,
+    scale_factor: f64,
+) -> (f64, f64) {
+    let (rectifying_radius, alpha) = kruger_coefficients();
+    let (_, xi_prime, eta_prime) = gauss_schreiber(latitude, longitude, central_meridian);
+
+    let mut xi = xi_prime;
+    let mut eta = eta_prime;
+    for (j, coefficient) in alpha.iter().enumerate() {
+        let k = 2.0 * (j + 1) as f64;
+        xi += coefficient * (k * xi_prime).sin() * (k * eta_prime).cosh();
+        eta += coefficient * (k * xi_prime).cos() * (k * eta_prime).sinh();
+    }
+
+    (
+        scale_factor * rectifying_radius * eta,
+        scale_factor * rectifying_radius * xi,
+    )
+}
+
+/// Converts latitude and longitude to UTM coordinates.
+///
+/// The zone is chosen with [`calculate_utm_zone`], so the Norway and Svalbard exceptions are
+/// honoured. The projection uses the Krüger series of the Transverse Mercator on the WGS84
+/// ellipsoid, which is accurate to well below a millimeter inside a zone.
+///
+/// # Returns
+/// - A `Result` containing `(zone, band, easting, northing)` with easting and northing in
+///   meters, or an error if the inputs are outside the valid latitude or longitude range.
+///   Eastings include the 500000 m false easting and southern hemisphere northings include
+///   the 10000000 m false northing.
+///
+/// # Examples
+/// ```
+/// use rust::lat_lon_to_utm_rust;
+///
+/// // Reference point in western Germany
+/// let (zone, band, easting, northing) = lat_lon_to_utm_rust(51.2, 7.5).unwrap();
+/// assert_eq!((zone, band), (32, 'U'));
+/// assert!((easting - 395201.31).abs() < 0.01);
+/// assert!((northing - 5673135.24).abs() < 0.01);
+///
+/// // New York City Hall
+/// let (zone, band, easting, northing) = lat_lon_to_utm_rust(40.71435, -74.00597).unwrap();
+/// assert_eq!((zone, band), (18, 'T'));
+/// assert!((easting - 583960.0).abs() < 1.0);
+/// assert!((northing - 4507523.0).abs() < 1.0);
+///
+/// // Southern hemisphere northings carry the false northing
+/// let (zone, band, _, northing) = lat_lon_to_utm_rust(-33.8688, 151.2093).unwrap();
+/// assert_eq!((zone, band), (56, 'H'));
+/// assert!(northing > 6_000_000.0);
+///
+/// // A point on the central meridian sits at the false easting
+/// let (_, _, easting, _) = lat_lon_to_utm_rust(45.0, 3.0).unwrap();
+/// assert!((easting - 500000.0).abs() < 1e-6);
+///
+/// // Error case: Latitude out of range
+/// assert!(lat_lon_to_utm_rust(-85.0, 0.0).is_err());
+/// ```
+pub fn lat_lon_to_utm_rust(
+    latitude: f64,
+    longitude: f64,
+) -> Result<(u32, char, f64, f64), UTMZoneError> {
+    lat_lon_to_utm_with_options_rust(latitude, longitude, &UtmOptions::default())
+}
+
+/// Converts latitude and longitude to UTM coordinates like [`lat_lon_to_utm_rust`], with the
+/// zone chosen by [`calculate_utm_zone_with_options`] and the projection using the options'
+/// scale factor.
+///
+/// # Returns
+/// - A `Result` containing `(zone, band, easting, northing)`, or an error if the position is
+///   rejected or the scale factor is not a positive finite number.
+///
+/// # Examples
+/// ```
+/// use rust::{lat_lon_to_utm_rust, lat_lon_to_utm_with_options_rust, UtmOptions};
+///
+/// // A unit scale factor stretches the grid away from the central meridian
+/// let unscaled = UtmOptions {
+///     scale_factor: 1.0,
+///     ..UtmOptions::default()
+/// };
+/// let (_, _, easting, northing) = lat_lon_to_utm_with_options_rust(51.2, 7.5, &unscaled).unwrap();
+/// let (_, _, e0, n0) = lat_lon_to_utm_rust(51.2, 7.5).unwrap();
+/// assert!(((easting - 500000.0) * 0.9996 - (e0 - 500000.0)).abs() < 1e-6);
+/// assert!((northing * 0.9996 - n0).abs() < 1e-6);
+///
+/// // Error case: Scale factor must be positive
+/// let invalid = UtmOptions {
+///     scale_factor: 0.0,
+///     ..UtmOptions::default()
+/// };
+/// assert!(lat_lon_to_utm_with_options_rust(51.2, 7.5, &invalid).is_err());
+/// ```
+pub fn lat_lon_to_utm_with_options_rust(
+    latitude: f64,
+    longitude: f64,
+    options: &UtmOptions,
+) -> Result<(u32, char, f64, f64), UTMZoneError> {
+    if !options.scale_factor.is_finite() || options.scale_factor <= 0.0 {
+        return Err(UTMZoneError::InvalidInput(format!(
+            "scale factor {} must be a positive finite number",
+            options.scale_factor
+        )));
+    }
+    let (zone_number, latitude_band) =
+        calculate_utm_zone_with_options(latitude, longitude, options)?;
+    let longitude = utm_longitude(latitude, longitude, options)?;
+
+    let (x, y) = transverse_mercator_forward(
+        latitude,
+        longitude,
+        utm_central_meridian(zone_number),
+        options.scale_factor,
+    );
+
+    let easting = x + UTM_FALSE_EASTING;
+    let northing = if !is_northern_hemisphere_rust(latitude) {
+        y + UTM_FALSE_NORTHING_SOUTH
+    } else {
+        y
+    };
+
+    Ok((zone_number, latitude_band, easting, northing))
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn lat_lon_to_utm(latitude: f64, longitude: f64) -> Result<JsValue, JsValue> {
+    match lat_lon_to_utm_rust(latitude, longitude) {
+        Ok((zone_number, latitude_band, easting, northing)) => {
+            let result = format!(
+                "{}{} {:.2} {:.2}",
+                zone_number, latitude_band, easting, northing
+            );
+            Ok(JsValue::from_str(&result))
+        }
+        Err(err) => Err(JsValue::from_str(&err.to_string())),
+    }
+}
+
+/// Coefficients of the inverse Krüger series for the WGS84 ellipsoid.
+///
+/// Returns the rectifying radius `A` and the `beta` coefficients of the inverse series,
+/// expanded to sixth order in the third flattening `n`.
+fn kruger_inverse_coefficients() -> (f64, [f64; 6]) {
+    let (rectifying_radius, _) = kruger_coefficients();
+    let f = Ellipsoid::WGS84.flattening();
+    let n = f / (2.0 - f);
+    let n2 = n * n;
+    let n3 = n2 * n;
+    let n4 = n3 * n;
+    let n5 = n4 * n;
+    let n6 = n5 * n;
+
+    let beta = [
+        n / 2.0 - 2.0 * n2 / 3.0 + 37.0 * n3 / 96.0 - n4 / 360.0 - 81.0 * n5 / 512.0
+            + 96199.0 * n6 / 604800.0,
+        n2 / 48.0 + n3 / 15.0 - 437.0 * n4 / 1440.0 + 46.0 * n5 / 105.0
+            - 1118711.0 * n6 / 3870720.0,
+        17.0 * n3 / 480.0 - 37.0 * n4 / 840.0 - 209.0 * n5 / 4480.0 + 5569.0 * n6 / 90720.0,
+        4397.0 * n4 / 161280.0 - 11.0 * n5 / 504.0 - 830251.0 * n6 / 7257600.0,
+        4583.0 * n5 / 161280.0 - 108847.0 * n6 / 3991680.0,
+        20648693.0 * n6 / 638668800.0,
+    ];
+
+    (rectifying_radius, beta)
+}
+
+/// Inverts the Transverse Mercator projection on the WGS84 ellipsoid.
+///
+/// Takes `(x, y)` in meters relative to the central meridian and the equator, without false
+/// easting or northing, and returns `(latitude, longitude)` in degrees.
+fn transverse_mercator_inverse(
+    x: f64,
+    y: f64,
+    central_meridian: f64,
+    scale_factor: f64,
+) -> (f64, f64) {
+    let e2 = Ellipsoid::WGS84.eccentricity_squared();
+    let e = e2.sqrt();
+    let (rectifying_radius, beta) = kruger_inverse_coefficients();
+
+    let xi = y / (scale_factor * rectifying_radius);
+    let eta = x / (scale_factor * rectifying_radius);
+
+    let mut xi_prime = xi;
+    let mut eta_prime = eta;
+    for (j, coefficient) in beta.iter().enumerate() {
+        let k = 2.0 * (j + 1) as f64;
+        xi_prime -= coefficient * (k * xi).sin() * (k * eta).cosh();
+        eta_prime -= coefficient * (k * xi).cos() * (k * eta).sinh();
+    }
+
+    // Back from Gauss-Schreiber coordinates to the conformal latitude and longitude
+    let tau_prime = xi_prime.sin() / (eta_prime.sinh().powi(2) + xi_prime.cos().powi(2)).sqrt();
+    let lambda = eta_prime.sinh().atan2(xi_prime.cos());
+
+    // Newton's method for the geodetic latitude from its conformal counterpart
+    let mut tau = tau_prime;
+    for _ in 0..10 {
+        let sigma = (e * (e * tau / (1.0 + tau * tau).sqrt()).atanh()).sinh();
+        let tau_i = tau * (1.0 + sigma * sigma).sqrt() - sigma * (1.0 + tau * tau).sqrt();
+        let delta = (tau_prime - tau_i) / (1.0 + tau_i * tau_i).sqrt()
+            * (1.0 + (1.0 - e2) * tau * tau)
+            / ((1.0 - e2) * (1.0 + tau * tau).sqrt());
+        tau += delta;
+        if delta.abs() < 1e-14 {
+            break;
+        }
+    }
+
+    (
+        tau.atan() * 180.0 / PI,
+        central_meridian + lambda * 180.0 / PI,
+    )
+}
+
+/// Calculates the UTM grid convergence at a point.
+///
+/// The grid convergence is the angle from true north to grid north. The zone is chosen with
+/// [`calculate_utm_zone`] and the angle is measured relative to that zone's central meridian.
+/// It is computed as the spherical term `atan(tan(Δλ) · sin(φ'))` on the conformal latitude
+/// `φ'` plus the correction from the Krüger series, which together are accurate to well below
+/// a micro-degree.
+///
+/// The sign follows that of `Δλ · sin(φ)`: in the northern hemisphere points east of the
+/// central meridian have a positive convergence and points west a negative one, and the signs
+/// are reversed in the southern hemisphere.
+///
+/// # Returns
+/// - The convergence angle in degrees, or an error if the inputs are outside the valid latitude
+///   or longitude range.
+///
+/// # Examples
+/// ```
+/// use rust::utm_convergence_rust;
+///
+/// // On the central meridian of zone 31 grid north is true north
+/// assert!(utm_convergence_rust(45.0, 3.0).unwrap().abs() < 1e-12);
+///
+/// // Close to the eastern boundary of the zone
+/// let gamma = utm_convergence_rust(45.0, 5.9).unwrap();
+/// assert!((gamma - 2.0515).abs() < 1e-4);
+///
+/// // West of the central meridian the angle is negative
+/// assert!(utm_convergence_rust(45.0, 0.5).unwrap() < 0.0);
+///
+/// // On the equator the convergence is zero everywhere
+/// assert!(utm_convergence_rust(0.0, 5.0).unwrap().abs() < 1e-12);
+///
+/// // Error case: Latitude out of range
+/// assert!(utm_convergence_rust(90.5, 0.0).is_err());
+/// ```
+pub fn utm_convergence_rust(latitude: f64, longitude: f64) -> Result<f64, UTMZoneError> {
+    let (zone_number, _) = calculate_utm_zone(latitude, longitude)?;
+    let longitude = wrap_longitude(longitude);
+    let central_meridian = utm_central_meridian(zone_number);
+
+    let (_, alpha) = kruger_coefficients();
+    let (tau_prime, xi_prime, eta_prime) = gauss_schreiber(latitude, longitude, central_meridian);
+    let lambda = (longitude - central_meridian) * PI / 180.0;
+
+    // Convergence of the spherical projection on the conformal latitude
+    let spherical = (tau_prime * lambda.tan() / (1.0 + tau_prime * tau_prime).sqrt()).atan();
+
+    // Correction from the derivative of the Krüger series
+    let mut p = 1.0;
+    let mut q = 0.0;
+    for (j, coefficient) in alpha.iter().enumerate() {
+        let k = 2.0 * (j + 1) as f64;
+        p += k * coefficient * (k * xi_prime).cos() * (k * eta_prime).cosh();
+        q += k * coefficient * (k * xi_prime).sin() * (k * eta_prime).sinh();
+    }
+    let correction = q.atan2(p);
+
+    Ok((spherical + correction) * 180.0 / PI)
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn utm_convergence(latitude: f64, longitude: f64) -> Result<f64, JsValue> {
+    utm_convergence_rust(latitude, longitude).map_err(|err| JsValue::from_str(&err.to_string()))
+}
+
+/// Returns the two letter MGRS 100 km grid square identifier for a UTM position.
+///
+/// Column letters run through `A`-`Z` (without `I` and `O`) in blocks of eight per zone, and
+/// row letters cycle through `A`-`V` every 2000 km, shifted by five letters in even zones.
+fn mgrs_100km_square(zone: u32, easting: f64, northing: f64) -> String {
+    const COLUMN_SETS: [&[u8; 8]; 3] = [b"ABCDEFGH", b"JKLMNPQR", b"STUVWXYZ"];
+    const ROW_LETTERS: &[u8; 20] = b"ABCDEFGHJKLMNPQRSTUV";
+
+    let set = (zone - 1) % 6;
+    let column_letters = COLUMN_SETS[(set % 3) as usize];
+    let column = (easting / 100000.0).floor() as usize;
+    let column_letter = column_letters[(column + 7) % 8] as char;
+
+    let row_offset = if set % 2 == 1 { 5 } else { 0 };
+    let row = (northing / 100000.0).floor() as usize;
+    let row_letter = ROW_LETTERS[(row + row_offset) % 20] as char;
+
+    format!("{}{}", column_letter, row_letter)
+}
+
+/// Calculates the UTM point scale factor `k` at a position.
+///
+/// The point scale is the ratio of a short distance on the UTM grid to the same distance on
+/// the ellipsoid, so ground distances are grid distances divided by `k`. It is 0.9996 on the
+/// central meridian of a zone and grows with the distance from it, passing 1 about 180 km out
+/// and reaching about 1.001 at the zone boundary on the equator. The zone is chosen with
+/// [`calculate_utm_zone`] and the value is computed exactly from the Krüger series rather than
+/// with the usual `k0 · (1 + (Δλ · cos φ)² / 2)` approximation.
+///
+/// # Returns
+/// - The dimensionless scale factor, or an error if the inputs are outside the valid latitude
+///   or longitude range.
+///
+/// # Examples
+/// ```
+/// use rust::utm_point_scale_rust;
+///
+/// // On the central meridian of zone 31 the scale is k0 at any latitude
+/// assert!((utm_point_scale_rust(0.0, 3.0).unwrap() - 0.9996).abs() < 1e-12);
+/// assert!((utm_point_scale_rust(45.0, 3.0).unwrap() - 0.9996).abs() < 1e-12);
+///
+/// // Near the eastern edge of the zone, close to the first order approximation
+/// let k = utm_point_scale_rust(45.0, 5.9).unwrap();
+/// let offset = 2.9_f64.to_radians() * 45.0_f64.to_radians().cos();
+/// assert!((k - 0.9996 * (1.0 + offset * offset / 2.0)).abs() < 1e-5);
+/// assert!(k > 1.0002 && k < 1.0004);
+///
+/// // The edge of the zone on the equator
+/// let k = utm_point_scale_rust(0.0, 5.999).unwrap();
+/// assert!((k - 1.000971).abs() < 1e-5);
+///
+/// // Error case: Latitude out of range
+/// assert!(utm_point_scale_rust(-91.0, 3.0).is_err());
+/// ```
+pub fn utm_point_scale_rust(latitude: f64, longitude: f64) -> Result<f64, UTMZoneError> {
+    let (zone_number, _) = calculate_utm_zone(latitude, longitude)?;
+    let longitude = wrap_longitude(longitude);
+    let central_meridian = utm_central_meridian(zone_number);
+
+    let e2 = Ellipsoid::WGS84.eccentricity_squared();
+    let (rectifying_radius, alpha) = kruger_coefficients();
+    let (tau_prime, xi_prime, eta_prime) = gauss_schreiber(latitude, longitude, central_meridian);
+    let tau = (latitude * PI / 180.0).tan();
+    let lambda = (longitude - central_meridian) * PI / 180.0;
+
+    // Scale of the conformal sphere and of the Gauss-Schreiber projection
+    let spherical = (1.0 + (1.0 - e2) * tau * tau).sqrt()
+        / (tau_prime * tau_prime + lambda.cos().powi(2)).sqrt();
+
+    // Scale from the derivative of the Krüger series
+    let mut p = 1.0;
+    let mut q = 0.0;
+    for (j, coefficient) in alpha.iter().enumerate() {
+        let k = 2.0 * (j + 1) as f64;
+        p += k * coefficient * (k * xi_prime).cos() * (k * eta_prime).cosh();
+        q += k * coefficient * (k * xi_prime).sin() * (k * eta_prime).sinh();
+    }
+    let series = rectifying_radius / Ellipsoid::WGS84.a * p.hypot(q);
+
+    Ok(UTM_SCALE_FACTOR * spherical * series)
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn utm_point_scale(latitude: f64, longitude: f64) -> Result<f64, JsValue> {
+    utm_point_scale_rust(latitude, longitude).map_err(|err| JsValue::from_str(&err.to_string()))
+}
+
+/// Finds the UTM zone, latitude band and MGRS 100 km grid square containing a position.
+///
+/// This is the prefix of a full MGRS reference, as returned by [`lat_lon_to_mgrs_rust`] with a
+/// precision of 0, split into its parts.
+///
+/// # Returns
+/// - A `Result` containing `(zone, band, square)`, where `square` holds the column and row
+///   letters, or an error if the position is out of range.
+///
+/// # Examples
+/// ```
+/// use rust::mgrs_grid_square_rust;
+///
+/// // New York City Hall
+/// assert_eq!(
+///     mgrs_grid_square_rust(40.71435, -74.00597),
+///     Ok((18, 'T', "WL".to_string()))
+/// );
+///
+/// // Sydney
+/// assert_eq!(
+///     mgrs_grid_square_rust(-33.8688, 151.2093),
+///     Ok((56, 'H', "LH".to_string()))
+/// );
+///
+/// // The letters I and O are never used, to avoid confusion with 1 and 0
+/// for lat in (-79..84).step_by(3) {
+///     for lon in (-179..180).step_by(2) {
+///         let (_, _, square) = mgrs_grid_square_rust(lat as f64, lon as f64).unwrap();
+///         assert!(!square.contains('I') && !square.contains('O'), "{}", square);
+///     }
+/// }
+///
+/// // Error case: Latitude out of range
+/// assert!(mgrs_grid_square_rust(95.0, 0.0).is_err());
+/// ```
+pub fn mgrs_grid_square_rust(
+    latitude: f64,
+    longitude: f64,
+) -> Result<(u32, char, String), UTMZoneError> {
+    let (zone_number, latitude_band, easting, northing) = lat_lon_to_utm_rust(latitude, longitude)?;
+    Ok((
+        zone_number,
+        latitude_band,
+        mgrs_100km_square(zone_number, easting, northing),
+    ))
+}
+
+/// Wasm entry point for [`mgrs_grid_square_rust`] returning `[zone, band, square]` as strings.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn mgrs_grid_square(latitude: f64, longitude: f64) -> Result<Vec<String>, JsValue> {
+    match mgrs_grid_square_rust(latitude, longitude) {
+        Ok((zone, band, square)) => Ok(vec![zone.to_string(), band.to_string(), square]),
+        Err(err) => Err(JsValue::from_str(&err.to_string())),
+    }
+}
+
+/// Converts latitude and longitude to a Military Grid Reference System (MGRS) string.
+///
+/// The reference is made of the UTM zone and latitude band, the 100 km grid square letters and
+/// `precision` digits each of easting and northing within that square. Digits are truncated,
+/// not rounded, so the reference names the square that contains the point.
+///
+/// # Parameters
+/// - `precision`: Number of digits for easting and northing, from 0 (100 km square) up to
+///   5 (1 m).
+///
+/// # Returns
+/// - A `Result` containing the MGRS string without spaces, or an error if the position is out
+///   of range or the precision is greater than 5.
+///
+/// # Examples
+/// ```
+/// use rust::lat_lon_to_mgrs_rust;
+///
+/// // Washington Monument to 10 m
+/// assert_eq!(lat_lon_to_mgrs_rust(38.8895, -77.0352, 4).unwrap(), "18SUJ23480648");
+///
+/// // New York City Hall to 1 m
+/// assert_eq!(lat_lon_to_mgrs_rust(40.71435, -74.00597, 5).unwrap(), "18TWL8395907523");
+///
+/// // Southern hemisphere
+/// assert_eq!(lat_lon_to_mgrs_rust(-33.8688, 151.2093, 3).unwrap(), "56HLH343509");
+///
+/// // Precision 0 gives only the zone, band and 100 km square
+/// assert_eq!(lat_lon_to_mgrs_rust(40.71435, -74.00597, 0).unwrap(), "18TWL");
+///
+/// // Error case: Precision too high
+/// assert!(lat_lon_to_mgrs_rust(40.71435, -74.00597, 6).is_err());
+/// ```
+pub fn lat_lon_to_mgrs_rust(
+    latitude: f64,
+    longitude: f64,
+    precision: u8,
+) -> Result<String, UTMZoneError> {
+    if precision > 5 {
+        return Err(UTMZoneError::InvalidPrecision(precision));
+    }
+
+    let (zone_number, latitude_band, easting, northing) = lat_lon_to_utm_rust(latitude, longitude)?;
+    let square = mgrs_100km_square(zone_number, easting, northing);
+
+    let divisor = 10f64.powi(5 - precision as i32);
+    let easting_digits = (easting % 100000.0 / divisor).floor() as u32;
+    let northing_digits = (northing % 100000.0 / divisor).floor() as u32;
+
+    let mut result = format!("{}{}{}", zone_number, latitude_band, square);
+    if precision > 0 {
+        let width = precision as usize;
+        result.push_str(&format!(
+            "{:0>width$}{:0>width$}",
+            easting_digits,
+            northing_digits,
+            width = width
+        ));
+    }
+
+    Ok(result)
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn lat_lon_to_mgrs(latitude: f64, longitude: f64, precision: u8) -> Result<JsValue, JsValue> {
+    match lat_lon_to_mgrs_rust(latitude, longitude, precision) {
+        Ok(result) => Ok(JsValue::from_str(&result)),
+        Err(err) => Err(JsValue::from_str(&err.to_string())),
+    }
+}
+
+/// Converts latitude and longitude to UTM and formats the result as a JSON object.
+///
+/// The object has the fields `zone` (number), `band` (one letter string), `easting` and
+/// `northing` (meters) and `hemisphere` (`"N"` or `"S"`). It is written by hand to keep the
+/// crate free of a serialization dependency.
+///
+/// # Returns
+/// - A `Result` containing the JSON text, or an error if the position is out of range.
+///
+/// # Examples
+/// ```
+/// use rust::lat_lon_to_utm_json_rust;
+///
+/// let json = lat_lon_to_utm_json_rust(51.2, 7.5).unwrap();
+/// assert!(json.starts_with(r#"{"zone":32,"band":"U","easting":395201.3"#));
+/// assert!(json.contains(r#","northing":5673135.2"#));
+/// assert!(json.ends_with(r#","hemisphere":"N"}"#));
+///
+/// let json = lat_lon_to_utm_json_rust(-33.8688, 151.2093).unwrap();
+/// assert!(json.starts_with(r#"{"zone":56,"band":"H","#));
+/// assert!(json.ends_with(r#","hemisphere":"S"}"#));
+///
+/// // Error case: Latitude out of range
+/// assert!(lat_lon_to_utm_json_rust(91.0, 0.0).is_err());
+/// ```
+pub fn lat_lon_to_utm_json_rust(latitude: f64, longitude: f64) -> Result<String, UTMZoneError> {
+    let (zone_number, latitude_band, easting, northing) = lat_lon_to_utm_rust(latitude, longitude)?;
+    let hemisphere = if is_northern_hemisphere_rust(latitude) {
+        'N'
+    } else {
+        'S'
+    };
+
+    Ok(format!(
+        r#"{{"zone":{},"band":"{}","easting":{},"northing":{},"hemisphere":"{}"}}"#,
+        zone_number, latitude_band, easting, northing, hemisphere
+    ))
+}
+
+/// Wasm entry point returning the UTM zone, band, easting, northing and hemisphere in one call,
+/// as the JSON text built by [`lat_lon_to_utm_json_rust`]. Use `JSON.parse` on the result.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn get_full_utm_from_lat_lon(latitude: f64, longitude: f64) -> Result<JsValue, JsValue> {
+    match lat_lon_to_utm_json_rust(latitude, longitude) {
+        Ok(json) => Ok(JsValue::from_str(&json)),
+        Err(err) => Err(JsValue::from_str(&err.to_string())),
+    }
+}
+
+/// Converts UTM coordinates to latitude and longitude.
+///
+/// This is the inverse of [`lat_lon_to_utm_rust`]. The latitude band only selects the
+/// hemisphere; southern bands ('C' to 'M') have the 10000000 m false northing removed.
+///
+/// # Returns
+/// - A `Result` containing `(latitude, longitude)` in degrees, or an error if the zone or band
+///   is invalid or the easting or northing is not finite.
+///
+/// # Examples
+/// ```
+/// use rust::{lat_lon_to_utm_rust, utm_to_lat_lon_rust};
+///
+/// let (lat, lon) = utm_to_lat_lon_rust(32, 'U', 395201.31, 5673135.24).unwrap();
+/// assert!((lat - 51.2).abs() < 1e-6);
+/// assert!((lon - 7.5).abs() < 1e-6);
+///
+/// // Round trip in the southern hemisphere
+/// let (zone, band, easting, northing) = lat_lon_to_utm_rust(-33.8688, 151.2093).unwrap();
+/// let (lat, lon) = utm_to_lat_lon_rust(zone, band, easting, northing).unwrap();
+/// assert!((lat + 33.8688).abs() < 1e-9);
+/// assert!((lon - 151.2093).abs() < 1e-9);
+///
+/// // Error cases
+/// assert!(utm_to_lat_lon_rust(0, 'U', 500000.0, 0.0).is_err());
+/// assert!(utm_to_lat_lon_rust(32, 'O', 500000.0, 0.0).is_err());
+/// assert!(utm_to_lat_lon_rust(32, 'U', f64::NAN, 0.0).is_err());
+/// ```
+pub fn utm_to_lat_lon_rust(
+    zone: u32,
+    band: char,
+    easting: f64,
+    northing: f64,
+) -> Result<(f64, f64), UTMZoneError> {
+    if !(1..=60).contains(&zone) {
+        return Err(UTMZoneError::InvalidZone(zone));
+    }
+    let northern = is_northern_band_rust(band)?;
+    if !easting.is_finite() || !northing.is_finite() {
+        return Err(UTMZoneError::InvalidInput(format!(
+            "easting {} and northing {} must be finite",
+            easting, northing
+        )));
+    }
+
+    let x = easting - UTM_FALSE_EASTING;
+    let y = if northern {
+        northing
+    } else {
+        northing - UTM_FALSE_NORTHING_SOUTH
+    };
+
+    let (latitude, longitude) =
+        transverse_mercator_inverse(x, y, utm_central_meridian(zone), UTM_SCALE_FACTOR);
+
+    Ok((latitude, wrap_longitude(longitude)))
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn utm_to_lat_lon(
+    zone: u32,
+    band: char,
+    easting: f64,
+    northing: f64,
+) -> Result<Vec<f64>, JsValue> {
+    match utm_to_lat_lon_rust(zone, band, easting, northing) {
+        Ok((latitude, longitude)) => Ok(vec![latitude, longitude]),
+        Err(err) => Err(JsValue::from_str(&err.to_string())),
+    }
+}
+
+/// Converts a Military Grid Reference System (MGRS) string to latitude and longitude.
+///
+/// The reference is made of a zone number, a latitude band, the two 100 km grid square letters
+/// and an even number of digits (0 to 10) split equally between easting and northing. Spaces
+/// are ignored and letters may be lowercase. Since a reference names a grid cell rather than a
+/// point, the center of that cell is returned, e.g. the center of the 1 m square for a
+/// 10 digit reference.
+///
+/// # Returns
+/// - A `Result` containing `(latitude, longitude)` in degrees, or a `UTMZoneError` describing
+///   why the reference is malformed.
+///
+/// # Examples
+/// ```
+/// use rust::{haversine_distance_rust, lat_lon_to_mgrs_rust, mgrs_to_lat_lon_rust};
+///
+/// // New York City Hall
+/// let (lat, lon) = mgrs_to_lat_lon_rust("18TWL8395907523").unwrap();
+/// let error = haversine_distance_rust(lat, lon, 40.71435, -74.00597).unwrap();
+/// assert!(error < 1.0);
+///
+/// // Round trip with spaces in the southern hemisphere
+/// let mgrs = lat_lon_to_mgrs_rust(-33.8688, 151.2093, 5).unwrap();
+/// assert_eq!(mgrs, "56HLH3436850948");
+/// let (lat, lon) = mgrs_to_lat_lon_rust("56H LH 34368 50948").unwrap();
+/// let error = haversine_distance_rust(lat, lon, -33.8688, 151.2093).unwrap();
+/// assert!(error < 1.0);
+///
+/// // Error cases: odd digit count, illegal letters, zone out of range
+/// assert!(mgrs_to_lat_lon_rust("18TWL839590752").is_err());
+/// assert!(mgrs_to_lat_lon_rust("18TWO8395907523").is_err());
+/// assert!(mgrs_to_lat_lon_rust("18IWL8395907523").is_err());
+/// assert!(mgrs_to_lat_lon_rust("61TWL8395907523").is_err());
+/// assert!(mgrs_to_lat_lon_rust("18TAL8395907523").is_err());
+/// assert!(mgrs_to_lat_lon_rust("18T").is_err());
+/// ```
+pub fn mgrs_to_lat_lon_rust(mgrs: &str) -> Result<(f64, f64), UTMZoneError> {
+    const COLUMN_SETS: [&str; 3] = ["ABCDEFGH", "JKLMNPQR", "STUVWXYZ"];
+    const ROW_LETTERS: &str = "ABCDEFGHJKLMNPQRSTUV";
+
+    let invalid = |reason: &str| {
+        UTMZoneError::InvalidInput(format!(
+            "'{}' is not a valid MGRS reference: {}",
+            mgrs, reason
+        ))
+    };
+
+    let text: String = mgrs
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+
+    let zone_digits = text.chars().take_while(|c| c.is_ascii_digit()).count();
+    if zone_digits == 0 || zone_digits > 2 {
+        return Err(invalid("it must start with a 1 or 2 digit zone number"));
+    }
+    let zone: u32 = text[..zone_digits]
+        .parse()
+        .map_err(|_| invalid("the zone number is malformed"))?;
+    if !(1..=60).contains(&zone) {
+        return Err(UTMZoneError::InvalidZone(zone));
+    }
+
+    let letters: Vec<char> = text[zone_digits..].chars().take(3).collect();
+    if letters.len() < 3 || !letters.iter().all(|c| c.is_ascii_uppercase()) {
+        return Err(invalid(
+            "the zone must be followed by a band letter and two grid square letters",
+        ));
+    }
+    let (band, column_letter, row_letter) = (letters[0], letters[1], letters[2]);
+    let northern = is_northern_band_rust(band)?;
+
+    let set = (zone - 1) % 6;
+    let column = COLUMN_SETS[(set % 3) as usize]
+        .find(column_letter)
+        .ok_or_else(|| invalid("the grid square column letter is not used in this zone"))?;
+    let row_offset = if set % 2 == 1 { 5 } else { 0 };
+    let row = ROW_LETTERS
+        .find(row_letter)
+        .ok_or_else(|| invalid("the grid square row letter must be between 'A' and 'V'"))?;
+
+    let digits = &text[zone_digits + 3..];
+    if !digits.len().is_multiple_of(2)
+        || digits.len() > 10
+        || !digits.chars().all(|c| c.is_ascii_digit())
+    {
+        return Err(invalid(
+            "the grid square must be followed by an even number of up to 10 digits",
+        ));
+    }
+    let precision = digits.len() / 2;
+    let cell_size = 10f64.powi(5 - precision as i32);
+    let (easting_digits, northing_digits) = digits.split_at(precision);
+    let easting_offset = easting_digits.parse::<f64>().unwrap_or(0.0) * cell_size;
+    let northing_offset = northing_digits.parse::<f64>().unwrap_or(0.0) * cell_size;
+
+    let easting = (column + 1) as f64 * 100000.0 + easting_offset + cell_size / 2.0;
+    let mut northing =
+        ((row + 20 - row_offset) % 20) as f64 * 100000.0 + northing_offset + cell_size / 2.0;
+
+    // The row letters repeat every 2000 km, so pick the cycle that falls inside the band. The
+    // band's southern edge on the central meridian is lowered by 100 km to allow for the
+    // curvature of the parallels across the zone.
+    let bands: Vec<char> = ('C'..='X').filter(|&c| c != 'I' && c != 'O').collect();
+    let band_index = bands.iter().position(|&c| c == band).unwrap_or(0);
+    let band_south_latitude = -80.0 + 8.0 * band_index as f64;
+    let (_, band_south_y) = transverse_mercator_forward(
+        band_south_latitude,
+        utm_central_meridian(zone),
+        utm_central_meridian(zone),
+        UTM_SCALE_FACTOR,
+    );
+    let band_south_northing = if northern {
+        band_south_y
+    } else {
+        band_south_y + UTM_FALSE_NORTHING_SOUTH
+    };
+    while northing < band_south_northing - 100000.0 {
+        northing += 2000000.0;
+    }
+
+    let band = if northern { 'N' } else { 'M' };
+    utm_to_lat_lon_rust(zone, band, easting, northing)
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn mgrs_to_lat_lon(mgrs: &str) -> Result<Vec<f64>, JsValue> {
+    match mgrs_to_lat_lon_rust(mgrs) {
+        Ok((latitude, longitude)) => Ok(vec![latitude, longitude]),
+        Err(err) => Err(JsValue::from_str(&err.to_string())),
+    }
+}
+
+//
+// Web Mercator (EPSG:3857)
+//
+
+/// Latitude in degrees at which the Web Mercator map becomes square. Latitudes beyond it are
+/// clamped, as EPSG:3857 does, so that the poles are not projected to infinity.
+const WEB_MERCATOR_MAX_LATITUDE: f64 = 85.05112878;
+
+/// Converts a latitude and longitude in degrees to spherical Web Mercator (EPSG:3857)
+/// coordinates, as used by slippy-map tile servers.
+///
+/// The projection uses a sphere with the WGS84 semi-major axis as radius. Latitudes beyond
+/// ±85.05112878° are clamped to that limit instead of being rejected, so every valid latitude
+/// maps onto the square world map. Longitudes are projected as given, without wrapping.
+///
+/// # Returns
+/// - A `Result` containing `(x, y)` in meters, or a `GeoError` if the latitude is outside
+///   [-90, 90] or the longitude is not finite.
+///
+/// # Examples
+/// ```
+/// use rust::lat_lon_to_web_mercator_rust;
+///
+/// assert_eq!(lat_lon_to_web_mercator_rust(0.0, 0.0), Ok((0.0, 0.0)));
+///
+/// let (x, y) = lat_lon_to_web_mercator_rust(85.05112878, 180.0).unwrap();
+/// assert!((x - 20037508.34).abs() < 0.01);
+/// assert!((y - 20037508.34).abs() < 0.01);
+///
+/// // Latitudes past the limit are clamped to it
+/// assert_eq!(
+///     lat_lon_to_web_mercator_rust(90.0, 0.0),
+///     lat_lon_to_web_mercator_rust(85.05112878, 0.0)
+/// );
+/// assert!(lat_lon_to_web_mercator_rust(91.0, 0.0).is_err());
+/// ```
+pub fn lat_lon_to_web_mercator_rust(latitude: f64, longitude: f64) -> Result<(f64, f64), GeoError> {
+    validate_lat_lon(latitude, longitude)?;
+
+    let radius = Ellipsoid::WGS84.a;
+    let latitude =
+        latitude.clamp(-WEB_MERCATOR_MAX_LATITUDE, WEB_MERCATOR_MAX_LATITUDE) * PI / 180.0;
+    let x = radius * longitude * PI / 180.0;
+    // atanh(sin φ) equals ln(tan(π/4 + φ/2)) but is exactly zero on the equator
+    let y = radius * latitude.sin().atanh();
+    Ok((x, y))
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn lat_lon_to_web_mercator(latitude: f64, longitude: f64) -> Result<Vec<f64>, JsValue> {
+    match lat_lon_to_web_mercator_rust(latitude, longitude) {
+        Ok((x, y)) => Ok(vec![x, y]),
+        Err(err) => Err(JsValue::from_str(&err.to_string())),
+    }
+}
+
+/// Converts spherical Web Mercator (EPSG:3857) coordinates in meters back to a latitude and
+/// longitude in degrees. This is the inverse of [`lat_lon_to_web_mercator_rust`].
+///
+/// # Returns
+/// - A `Result` containing `(latitude, longitude)` in decimal degrees, or
+///   `GeoError::InvalidInput` if either coordinate is not finite.
+///
+/// # Examples
+/// ```
+/// use rust::{lat_lon_to_web_mercator_rust, web_mercator_to_lat_lon_rust};
+///
+/// assert_eq!(web_mercator_to_lat_lon_rust(0.0, 0.0), Ok((0.0, 0.0)));
+///
+/// let (x, y) = lat_lon_to_web_mercator_rust(51.5074, -0.1278).unwrap();
+/// let (lat, lon) = web_mercator_to_lat_lon_rust(x, y).unwrap();
+/// assert!((lat - 51.5074).abs() < 1e-9 && (lon + 0.1278).abs() < 1e-9);
+///
+/// assert!(web_mercator_to_lat_lon_rust(f64::NAN, 0.0).is_err());
+/// ```
+pub fn web_mercator_to_lat_lon_rust(x: f64, y: f64) -> Result<(f64, f64), GeoError> {
+    require_finite(&[("x", x), ("y", y)])?;
+
+    let radius = Ellipsoid::WGS84.a;
+    let latitude = (y / radius).sinh().atan() * 180.0 / PI;
+    let longitude = x / radius * 180.0 / PI;
+    Ok((latitude, longitude))
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn web_mercator_to_lat_lon(x: f64, y: f64) -> Result<Vec<f64>, JsValue> {
+    match web_mercator_to_lat_lon_rust(x, y) {
+        Ok((latitude, longitude)) => Ok(vec![latitude, longitude]),
+        Err(err) => Err(JsValue::from_str(&err.to_string())),
+    }
+}
+
+//
+// Great-circle navigation on a spherical Earth
+//
+
+/// Mean Earth radius in meters (IUGG R1), used for spherical calculations.
+const EARTH_MEAN_RADIUS: f64 = 6371008.8;
+
+/// Calculates the great-circle distance between two points using the Haversine formula.
+///
+/// The Earth is modelled as a sphere with the mean radius of 6371008.8 meters, which keeps
+/// the error below roughly 0.5% compared to the ellipsoidal distance.
+///
+/// # Parameters
+/// - `lat1`, `lon1`: Latitude and longitude of the first point in degrees.
+/// - `lat2`, `lon2`: Latitude and longitude of the second point in degrees.
+///
+/// # Returns
+/// - The distance in meters, or a `GeoError` if any coordinate is out of range.
+///
+/// # Examples
+/// ```
+/// use rust::{haversine_distance_rust, GeoError};
+///
+/// // Identical points are exactly zero meters apart
+/// assert_eq!(haversine_distance_rust(40.0, -75.0, 40.0, -75.0), Ok(0.0));
+///
+/// // Antipodal points are half a circumference apart
+/// let d = haversine_distance_rust(0.0, 0.0, 0.0, 180.0).unwrap();
+/// assert!((d - std::f64::consts::PI * 6371008.8).abs() < 1e-6);
+///
+/// // Paris to London is about 343.5 km
+/// let d = haversine_distance_rust(48.8566, 2.3522, 51.5074, -0.1278).unwrap();
+/// assert!((d - 343_500.0).abs() / 343_500.0 < 0.005);
+///
+/// // Error case: Latitude out of range
+/// assert_eq!(
+///     haversine_distance_rust(91.0, 0.0, 0.0, 0.0),
+///     Err(GeoError::InvalidLatitude(91.0))
+/// );
+///
+/// // Longitudes wrap around the antimeridian
+/// let wrapped = haversine_distance_rust(0.0, 0.0, 0.0, -180.5).unwrap();
+/// let direct = haversine_distance_rust(0.0, 0.0, 0.0, 179.5).unwrap();
+/// assert!((wrapped - direct).abs() < 1e-6);
+///
+/// // Error case: Longitude is infinite
+/// assert_eq!(
+///     haversine_distance_rust(0.0, 0.0, 0.0, f64::INFINITY),
+///     Err(GeoError::InvalidInput(
+///         "longitude must be a finite number, got inf".to_string()
+///     ))
+/// );
+/// ```
+pub fn haversine_distance_rust(
+    lat1: f64,
+    lon1: f64,
+    lat2: f64,
+    lon2: f64,
+) -> Result<f64, GeoError> {
+    validate_lat_lon(lat1, lon1)?;
+    validate_lat_lon(lat2, lon2)?;
+
+    let phi1 = lat1 * PI / 180.0;
+    let phi2 = lat2 * PI / 180.0;
+    let delta_phi = (lat2 - lat1) * PI / 180.0;
+    let delta_lambda = (lon2 - lon1) * PI / 180.0;
+
+    let h = (delta_phi / 2.0).sin().powi(2)
+        + phi1.cos() * phi2.cos() * (delta_lambda / 2.0).sin().powi(2);
+    // Rounding can push h slightly above 1 for antipodal points
+    let h = h.min(1.0);
+
+    Ok(2.0 * EARTH_MEAN_RADIUS * h.sqrt().atan2((1.0 - h).sqrt()))
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn haversine_distance(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> Result<f64, JsValue> {
+    haversine_distance_rust(lat1, lon1, lat2, lon2)
+        .map_err(|err| JsValue::from_str(&err.to_string()))
+}
+
+/// Maximum number of iterations of the Vincenty inverse formula before giving up
+const VINCENTY_MAX_ITERATIONS: usize = 200;
+
+/// Calculates the geodesic distance between two points on the WGS84 ellipsoid using the inverse
+/// Vincenty formula.
+///
+/// The iteration on the longitude difference on the auxiliary sphere stops once it changes by
+/// less than 1e-12 radians, which is well below a millimeter on the ground. For nearly
+/// antipodal points the formula may fail to converge; rather than loop forever the function
+/// gives up after 200 iterations and returns `GeoError::NonConvergence`.
+///
+/// # Parameters
+/// - `lat1`, `lon1`: Latitude and longitude of the first point in degrees.
+/// - `lat2`, `lon2`: Latitude and longitude of the second point in degrees.
+///
+/// # Returns
+/// - The ellipsoidal distance in meters, or a `GeoError` if any coordinate is out of range or
+///   the iteration does not converge.
+///
+/// # Examples
+/// ```
+/// use rust::{vincenty_distance_rust, GeoError};
+///
+/// // Flinders Peak to Buninyong, the reference line published by Geoscience Australia
+/// let lat1 = -(37.0 + 57.0 / 60.0 + 3.72030 / 3600.0);
+/// let lon1 = 144.0 + 25.0 / 60.0 + 29.52440 / 3600.0;
+/// let lat2 = -(37.0 + 39.0 / 60.0 + 10.15610 / 3600.0);
+/// let lon2 = 143.0 + 55.0 / 60.0 + 35.38390 / 3600.0;
+/// let d = vincenty_distance_rust(lat1, lon1, lat2, lon2).unwrap();
+/// assert!((d - 54972.271).abs() < 1e-3);
+///
+/// // A quarter of the equator is a quarter of the equatorial circumference
+/// let d = vincenty_distance_rust(0.0, 0.0, 0.0, 90.0).unwrap();
+/// assert!((d - 6378137.0 * std::f64::consts::FRAC_PI_2).abs() < 1e-3);
+///
+/// // Identical points
+/// assert_eq!(vincenty_distance_rust(10.0, 20.0, 10.0, 20.0), Ok(0.0));
+///
+/// // Nearly antipodal points do not converge and report an error instead of hanging
+/// assert_eq!(
+///     vincenty_distance_rust(0.0, 0.0, 0.5, 179.7),
+///     Err(GeoError::NonConvergence)
+/// );
+///
+/// // Error case: Latitude out of range
+/// assert_eq!(
+///     vincenty_distance_rust(0.0, 0.0, -90.5, 0.0),
+///     Err(GeoError::InvalidLatitude(-90.5))
+/// );
+/// ```
+pub fn vincenty_distance_rust(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> Result<f64, GeoError> {
+    validate_lat_lon(lat1, lon1)?;
+    validate_lat_lon(lat2, lon2)?;
+
+    let ellipsoid = Ellipsoid::WGS84;
+    let a = ellipsoid.a;
+    let f = ellipsoid.flattening();
+    let b = ellipsoid.semi_minor_axis();
+
+    // Reduced latitudes on the auxiliary sphere
+    let u1 = ((1.0 - f) * (lat1 * PI / 180.0).tan()).atan();
+    let u2 = ((1.0 - f) * (lat2 * PI / 180.0).tan()).atan();
+    let (sin_u1, cos_u1) = u1.sin_cos();
+    let (sin_u2, cos_u2) = u2.sin_cos();
+
+    let l = (lon2 - lon1) * PI / 180.0;
+    let mut lambda = l;
+
+    for _ in 0..VINCENTY_MAX_ITERATIONS {
+        let (sin_lambda, cos_lambda) = lambda.sin_cos();
+        let sin_sigma = ((cos_u2 * sin_lambda).powi(2)
+            + (cos_u1 * sin_u2 - sin_u1 * cos_u2 * cos_lambda).powi(2))
+        .sqrt();
+        if sin_sigma == 0.0 {
+            // Coincident points
+            return Ok(0.0);
+        }
+        let cos_sigma = sin_u1 * sin_u2 + cos_u1 * cos_u2 * cos_lambda;
+        let sigma = sin_sigma.atan2(cos_sigma);
+        let sin_alpha = cos_u1 * cos_u2 * sin_lambda / sin_sigma;
+        let cos2_alpha = 1.0 - sin_alpha * sin_alpha;
+        // On the equator cos2_alpha is zero and the midpoint term vanishes
+        let cos_2sigma_m = if cos2_alpha == 0.0 {
+            0.0
+        } else {
+            cos_sigma - 2.0 * sin_u1 * sin_u2 / cos2_alpha
+        };
+        let c = f / 16.0 * cos2_alpha * (4.0 + f * (4.0 - 3.0 * cos2_alpha));
+
+        let previous_lambda = lambda;
+        lambda = l
+            + (1.0 - c)
+                * f
+                * sin_alpha
+                * (sigma
+                    + c * sin_sigma
+                        * (cos_2sigma_m + c * cos_sigma * (-1.0 + 2.0 * cos_2sigma_m.powi(2))));
+
+        if (lambda - previous_lambda).abs() < 1e-12 {
+            let u_sq = cos2_alpha * (a * a - b * b) / (b * b);
+            let big_a =
+                1.0 + u_sq / 16384.0 * (4096.0 + u_sq * (-768.0 + u_sq * (320.0 - 175.0 * u_sq)));
+            let big_b = u_sq / 1024.0 * (256.0 + u_sq * (-128.0 + u_sq * (74.0 - 47.0 * u_sq)));
+            let delta_sigma = big_b
+                * sin_sigma
+                * (cos_2sigma_m
+                    + big_b / 4.0
+                        * (cos_sigma * (-1.0 + 2.0 * cos_2sigma_m.powi(2))
+                            - big_b / 6.0
+                                * cos_2sigma_m
+                                * (-3.0 + 4.0 * sin_sigma.powi(2))
+                                * (-3.0 + 4.0 * cos_2sigma_m.powi(2))));
+
+            return Ok(b * big_a * (sigma - delta_sigma));
+        }
+    }
+
+    Err(GeoError::NonConvergence)
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn vincenty_distance(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> Result<f64, JsValue> {
+    vincenty_distance_rust(lat1, lon1, lat2, lon2)
+        .map_err(|err| JsValue::from_str(&err.to_string()))
+}
+
+/// Wraps an angle in degrees into the range [0, 360)
+fn normalize_bearing(bearing: f64) -> f64 {
+    let normalized = bearing.rem_euclid(360.0);
+    // rem_euclid can round up to exactly 360 for tiny negative inputs
+    if normalized >= 360.0 {
+        0.0
+    } else {
+        normalized
+    }
+}
+
+/// Calculates the initial bearing (forward azimuth) from one point to another along the great
+/// circle joining them.
+///
+/// The bearing is measured clockwise from true north, so due north is 0 and due east is 90.
+/// It generally changes along the path; this is the heading at the starting point. When the
+/// two points are identical the bearing is undefined and `0.0` is returned.
+///
+/// # Parameters
+/// - `lat1`, `lon1`: Latitude and longitude of the starting point in degrees.
+/// - `lat2`, `lon2`: Latitude and longitude of the destination in degrees.
+///
+/// # Returns
+/// - The bearing in degrees in the range [0, 360), or a `GeoError` if any coordinate is out
+///   of range.
+///
+/// # Examples
+/// ```
+/// use rust::{initial_bearing_rust, GeoError};
+///
+/// // The four cardinal directions from the equator
+/// assert_eq!(initial_bearing_rust(0.0, 0.0, 10.0, 0.0), Ok(0.0));
+/// assert_eq!(initial_bearing_rust(0.0, 0.0, 0.0, 10.0), Ok(90.0));
+/// assert_eq!(initial_bearing_rust(0.0, 0.0, -10.0, 0.0), Ok(180.0));
+/// assert_eq!(initial_bearing_rust(0.0, 0.0, 0.0, -10.0), Ok(270.0));
+///
+/// // Identical points
+/// assert_eq!(initial_bearing_rust(51.5, -0.1, 51.5, -0.1), Ok(0.0));
+///
+/// // London to Paris heads south-east
+/// let bearing = initial_bearing_rust(51.5074, -0.1278, 48.8566, 2.3522).unwrap();
+/// assert!((bearing - 148.1).abs() < 0.1);
+///
+/// // Longitudes wrap around: 200 degrees east is 160 degrees west
+/// let bearing = initial_bearing_rust(0.0, 0.0, 0.0, 200.0).unwrap();
+/// assert!((bearing - 270.0).abs() < 1e-9);
+///
+/// // Error case: Longitude is infinite
+/// assert!(matches!(
+///     initial_bearing_rust(0.0, 0.0, 0.0, f64::NEG_INFINITY),
+///     Err(GeoError::InvalidInput(_))
+/// ));
+/// ```
+pub fn initial_bearing_rust(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> Result<f64, GeoError> {
+    validate_lat_lon(lat1, lon1)?;
+    validate_lat_lon(lat2, lon2)?;
+
+    let phi1 = lat1 * PI / 180.0;
+    let phi2 = lat2 * PI / 180.0;
+    let delta_lambda = (lon2 - lon1) * PI / 180.0;
+
+    let y = delta_lambda.sin() * phi2.cos();
+    let x = phi1.cos() * phi2.sin() - phi1.sin() * phi2.cos() * delta_lambda.cos();
+
+    Ok(normalize_bearing(y.atan2(x) * 180.0 / PI))
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn initial_bearing(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> Result<f64, JsValue> {
+    initial_bearing_rust(lat1, lon1, lat2, lon2).map_err(|err| JsValue::from_str(&err.to_string()))
+}
+
+/// Converts a magnetic (compass) bearing to a true bearing.
+///
+/// The magnetic declination is supplied by the caller, for example from a chart or a
+/// geomagnetic model, and is positive when magnetic north lies east of true north.
+///
+/// # Returns
+/// - A `Result` containing the true bearing in degrees in the range [0, 360), or
+///   `GeoError::InvalidInput` if either input is NaN or infinite.
+///
+/// # Examples
+/// ```
+/// use rust::{magnetic_to_true_bearing_rust, GeoError};
+///
+/// assert_eq!(magnetic_to_true_bearing_rust(0.0, 10.0), Ok(10.0));
+/// assert_eq!(magnetic_to_true_bearing_rust(355.0, 10.0), Ok(5.0));
+/// assert_eq!(magnetic_to_true_bearing_rust(5.0, -10.0), Ok(355.0));
+/// assert!(matches!(
+///     magnetic_to_true_bearing_rust(f64::NAN, 10.0),
+///     Err(GeoError::InvalidInput(_))
+/// ));
+/// ```
+pub fn magnetic_to_true_bearing_rust(
+    magnetic_bearing: f64,
+    declination: f64,
+) -> Result<f64, GeoError> {
+    require_finite(&[
+        ("magnetic bearing", magnetic_bearing),
+        ("declination", declination),
+    ])?;
+    Ok(normalize_bearing(magnetic_bearing + declination))
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn magnetic_to_true_bearing(magnetic_bearing: f64, declination: f64) -> Result<f64, JsValue> {
+    magnetic_to_true_bearing_rust(magnetic_bearing, declination)
+        .map_err(|err| JsValue::from_str(&err.to_string()))
+}
+
+/// Converts a true bearing to a magnetic (compass) bearing.
+///
+/// This is the inverse of [`magnetic_to_true_bearing_rust`], with the declination positive
+/// east.
+///
+/// # Returns
+/// - A `Result` containing the magnetic bearing in degrees in the range [0, 360), or
+///   `GeoError::InvalidInput` if either input is NaN or infinite.
+///
+/// # Examples
+/// ```
+/// use rust::{true_to_magnetic_bearing_rust, GeoError};
+///
+/// assert_eq!(true_to_magnetic_bearing_rust(10.0, 10.0), Ok(0.0));
+/// assert_eq!(true_to_magnetic_bearing_rust(5.0, 10.0), Ok(355.0));
+/// assert_eq!(true_to_magnetic_bearing_rust(355.0, -10.0), Ok(5.0));
+/// assert!(matches!(
+///     true_to_magnetic_bearing_rust(10.0, f64::INFINITY),
+///     Err(GeoError::InvalidInput(_))
+/// ));
+/// ```
+pub fn true_to_magnetic_bearing_rust(true_bearing: f64, declination: f64) -> Result<f64, GeoError> {
+    require_finite(&[("true bearing", true_bearing), ("declination", declination)])?;
+    Ok(normalize_bearing(true_bearing - declination))
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn true_to_magnetic_bearing(true_bearing: f64, declination: f64) -> Result<f64, JsValue> {
+    true_to_magnetic_bearing_rust(true_bearing, declination)
+        .map_err(|err| JsValue::from_str(&err.to_string()))
+}
+
+/// Calculates the point reached by travelling a given distance along a great circle from a
+/// starting point with a given initial bearing.
+///
+/// This is the direct problem on a sphere of radius 6371008.8 m and the inverse of
+/// [`haversine_distance_rust`] together with [`initial_bearing_rust`], which makes it suitable
+/// for dead reckoning.
+///
+/// # Parameters
+/// - `latitude`, `longitude`: Starting point in degrees.
+/// - `bearing`: Initial bearing in degrees clockwise from true north.
+/// - `distance`: Distance to travel in meters.
+///
+/// # Returns
+/// - A `Result` containing `(latitude, longitude)` of the destination in degrees, with the
+///   longitude in [-180, 180), or a `GeoError` if the start is out of range or the bearing or
+///   distance is not finite.
+///
+/// # Examples
+/// ```
+/// use rust::{destination_point_rust, haversine_distance_rust, initial_bearing_rust};
+///
+/// // One degree of latitude due north of the equator
+/// let (lat, lon) = destination_point_rust(0.0, 0.0, 0.0, 111195.0).unwrap();
+/// assert!((lat - 1.0).abs() < 1e-4);
+/// assert!(lon.abs() < 1e-9);
+///
+/// // Measuring back to the origin recovers the distance and bearing
+/// let (lat, lon) = destination_point_rust(51.5074, -0.1278, 148.1, 343_000.0).unwrap();
+/// let d = haversine_distance_rust(51.5074, -0.1278, lat, lon).unwrap();
+/// assert!((d - 343_000.0).abs() < 1e-6);
+/// let bearing = initial_bearing_rust(51.5074, -0.1278, lat, lon).unwrap();
+/// assert!((bearing - 148.1).abs() < 1e-9);
+///
+/// // The longitude wraps across the antimeridian
+/// let (_, lon) = destination_point_rust(0.0, 179.5, 90.0, 111195.0).unwrap();
+/// assert!((lon + 179.5).abs() < 1e-4);
+///
+/// // Error cases
+/// assert!(destination_point_rust(91.0, 0.0, 0.0, 1.0).is_err());
+/// assert!(destination_point_rust(0.0, 0.0, f64::NAN, 1.0).is_err());
+/// ```
+pub fn destination_point_rust(
+    latitude: f64,
+    longitude: f64,
+    bearing: f64,
+    distance: f64,
+) -> Result<(f64, f64), GeoError> {
+    validate_lat_lon(latitude, longitude)?;
+    if !bearing.is_finite() || !distance.is_finite() {
+        return Err(GeoError::InvalidInput(format!(
+            "bearing {} and distance {} must be finite",
+            bearing, distance
+        )));
+    }
+
+    let phi1 = latitude * PI / 180.0;
+    let lambda1 = longitude * PI / 180.0;
+    let theta = bearing * PI / 180.0;
+    let delta = distance / EARTH_MEAN_RADIUS;
+
+    let sin_phi2 = phi1.sin() * delta.cos() + phi1.cos() * delta.sin() * theta.cos();
+    // Rounding can push the sine slightly outside [-1, 1] near the poles
+    let phi2 = sin_phi2.clamp(-1.0, 1.0).asin();
+    let lambda2 = lambda1
+        + (theta.sin() * delta.sin() * phi1.cos()).atan2(delta.cos() - phi1.sin() * sin_phi2);
+
+    Ok((phi2 * 180.0 / PI, wrap_longitude(lambda2 * 180.0 / PI)))
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn destination_point(
+    latitude: f64,
+    longitude: f64,
+    bearing: f64,
+    distance: f64,
+) -> Result<Vec<f64>, JsValue> {
+    match destination_point_rust(latitude, longitude, bearing, distance) {
+        Ok((latitude, longitude)) => Ok(vec![latitude, longitude]),
+        Err(err) => Err(JsValue::from_str(&err.to_string())),
+    }
+}
+
+/// Parses a coordinate written in degrees, minutes and seconds into decimal degrees.
+///
+/// Minutes and seconds are optional, and the components can be separated by the usual
+/// `°`, `'` and `"` symbols (or their typographic variants `′` and `″`), by whitespace, or
+/// both. A trailing or leading hemisphere letter `N`, `S`, `E` or `W` sets the sign, with
+/// south and west being negative. A leading minus sign is accepted instead of a hemisphere.
+///
+/// # Returns
+/// - The angle in decimal degrees, or `GeoError::InvalidInput` if the text cannot be parsed,
+///   minutes or seconds are not below 60, or the angle is out of range for its hemisphere.
+///
+/// # Examples
+/// ```
+/// use rust::dms_to_decimal_rust;
+///
+/// let lat = dms_to_decimal_rust("40°26'46\"N").unwrap();
+/// assert!((lat - 40.446111).abs() < 1e-6);
+///
+/// // South and west are negative
+/// let lat = dms_to_decimal_rust("33° 52' 4.8\" S").unwrap();
+/// assert!((lat + 33.868).abs() < 1e-9);
+/// let lon = dms_to_decimal_rust("W 79 58 56").unwrap();
+/// assert!((lon + 79.982222).abs() < 1e-6);
+///
+/// // Symbols and hemisphere are optional
+/// assert_eq!(dms_to_decimal_rust("-10 30").unwrap(), -10.5);
+/// assert_eq!(dms_to_decimal_rust("12.25").unwrap(), 12.25);
+///
+/// // Error cases
+/// assert!(dms_to_decimal_rust("40°61'00\"N").is_err());
+/// assert!(dms_to_decimal_rust("91°00'00\"N").is_err());
+/// assert!(dms_to_decimal_rust("-40°26'46\"S").is_err());
+/// assert!(dms_to_decimal_rust("north").is_err());
+/// ```
+pub fn dms_to_decimal_rust(dms: &str) -> Result<f64, GeoError> {
+    let invalid = || GeoError::InvalidInput(format!("'{}' is not a valid DMS coordinate", dms));
+
+    let mut text = dms.trim();
+    let mut hemisphere = None;
+    if let Some(last) = text.chars().last().filter(|c| "NSEWnsew".contains(*c)) {
+        hemisphere = Some(last.to_ascii_uppercase());
+        text = &text[..text.len() - 1];
+    } else if let Some(first) = text.chars().next().filter(|c| "NSEWnsew".contains(*c)) {
+        hemisphere = Some(first.to_ascii_uppercase());
+        text = &text[1..];
+    }
+
+    let text = text.trim();
+    let (negative, text) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text),
+    };
+    if negative && hemisphere.is_some() {
+        return Err(invalid());
+    }
+
+    let components = text
+        .split(|c: char| c.is_whitespace() || "°º'\"′″".contains(c))
+        .filter(|part| !part.is_empty())
+        .map(|part| part.parse::<f64>().map_err(|_| invalid()))
+        .collect::<Result<Vec<f64>, GeoError>>()?;
+
+    let (degrees, minutes, seconds) = match components[..] {
+        [d] => (d, 0.0, 0.0),
+        [d, m] => (d, m, 0.0),
+        [d, m, s] => (d, m, s),
+        _ => return Err(invalid()),
+    };
+    if [degrees, minutes, seconds]
+        .iter()
+        .any(|v| !v.is_finite() || *v < 0.0)
+        || minutes >= 60.0
+        || seconds >= 60.0
+    {
+        return Err(invalid());
+    }
+
+    let magnitude = degrees + minutes / 60.0 + seconds / 3600.0;
+    let limit = match hemisphere {
+        Some('N') | Some('S') => 90.0,
+        _ => 180.0,
+    };
+    if magnitude > limit {
+        return Err(invalid());
+    }
+
+    if negative || matches!(hemisphere, Some('S') | Some('W')) {
+        Ok(-magnitude)
+    } else {
+        Ok(magnitude)
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn dms_to_decimal(dms: &str) -> Result<f64, JsValue> {
+    dms_to_decimal_rust(dms).map_err(|err| JsValue::from_str(&err.to_string()))
+}
+
+/// Formats decimal degrees as degrees, minutes and whole seconds with a hemisphere letter.
+///
+/// Seconds are rounded to the nearest whole second, carrying into the minutes and degrees
+/// when needed, so the output never shows 60 seconds or 60 minutes.
+///
+/// # Parameters
+/// - `decimal`: The angle in decimal degrees.
+/// - `is_latitude`: Selects `N`/`S` for latitudes or `E`/`W` for longitudes. Zero is
+///   reported as `N` or `E`.
+///
+/// # Returns
+/// - A `Result` containing the formatted angle, or a `GeoError` as described for
+///   [`decimal_to_dms_precise_rust`].
+///
+/// # Examples
+/// ```
+/// use rust::{decimal_to_dms_rust, dms_to_decimal_rust};
+///
+/// assert_eq!(decimal_to_dms_rust(40.446111, true), Ok("40°26'46\"N".to_string()));
+/// assert_eq!(decimal_to_dms_rust(-79.982222, false), Ok("79°58'56\"W".to_string()));
+/// assert_eq!(decimal_to_dms_rust(-33.868, true), Ok("33°52'05\"S".to_string()));
+/// assert_eq!(decimal_to_dms_rust(0.0, false), Ok("0°00'00\"E".to_string()));
+///
+/// // Rounding carries into the next minute
+/// assert_eq!(decimal_to_dms_rust(10.9999, true), Ok("11°00'00\"N".to_string()));
+///
+/// // Round trip to within half a second
+/// let formatted = decimal_to_dms_rust(-151.2093, false).unwrap();
+/// let parsed = dms_to_decimal_rust(&formatted).unwrap();
+/// assert!((parsed + 151.2093).abs() < 0.5 / 3600.0);
+///
+/// assert!(decimal_to_dms_rust(f64::NAN, true).is_err());
+/// ```
+pub fn decimal_to_dms_rust(decimal: f64, is_latitude: bool) -> Result<String, GeoError> {
+    decimal_to_dms_precise_rust(decimal, is_latitude, 0)
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn decimal_to_dms(decimal: f64, is_latitude: bool) -> Result<String, JsValue> {
+    decimal_to_dms_rust(decimal, is_latitude).map_err(|err| JsValue::from_str(&err.to_string()))
+}
+
+/// Largest number of decimal places on the seconds field. Beyond this the digits are below the
+/// precision of an `f64` angle.
+const DMS_MAX_SECONDS_DECIMALS: usize = 9;
+
+/// Formats decimal degrees as degrees, minutes and seconds with a chosen number of decimal
+/// places on the seconds.
+///
+/// The angle is rounded once, to the nearest unit of the last seconds digit, and the result is
+/// split into degrees, minutes and seconds with exact integer arithmetic. Rounding therefore
+/// carries into the minutes and degrees instead of printing 60 seconds or 60 minutes. A
+/// latitude just below 90° that rounds up is shown as `90°00'00"`, the correctly rounded and
+/// still valid value, rather than as `89°59'60"`; ask for more decimals to see the difference.
+/// `seconds_decimals` above 9 is treated as 9.
+///
+/// # Returns
+/// - A `Result` containing the formatted angle, `GeoError::InvalidInput` if `decimal` is NaN
+///   or infinite, or `InvalidLatitude`/`InvalidLongitude` if it is outside the [-90, 90] or
+///   [-180, 180] range that [`dms_to_decimal_rust`] accepts back.
+///
+/// # Examples
+/// ```
+/// use rust::{decimal_to_dms_precise_rust, GeoError};
+///
+/// let dms = |decimal, is_latitude, decimals| {
+///     decimal_to_dms_precise_rust(decimal, is_latitude, decimals).unwrap()
+/// };
+/// assert_eq!(dms(40.446111, true, 2), "40°26'46.00\"N");
+/// assert_eq!(dms(-79.982222, false, 1), "79°58'56.0\"W");
+/// assert_eq!(dms(-33.868, true, 0), "33°52'05\"S");
+///
+/// // 59.9996" rounds into the next minute
+/// assert_eq!(dms(10.0 + 59.9996 / 3600.0, true, 3), "10°01'00.000\"N");
+///
+/// // 59'59.9996" rounds into the next degree
+/// assert_eq!(dms(10.0 + 3599.9996 / 3600.0, false, 3), "11°00'00.000\"E");
+///
+/// // Close to the pole
+/// assert_eq!(dms(89.99999, true, 0), "90°00'00\"N");
+/// assert_eq!(dms(89.99999, true, 2), "89°59'59.96\"N");
+///
+/// // Error cases: non-finite and out of range angles
+/// assert!(matches!(
+///     decimal_to_dms_precise_rust(f64::NAN, false, 2),
+///     Err(GeoError::InvalidInput(_))
+/// ));
+/// assert!(matches!(
+///     decimal_to_dms_precise_rust(f64::INFINITY, true, 0),
+///     Err(GeoError::InvalidInput(_))
+/// ));
+/// assert_eq!(
+///     decimal_to_dms_precise_rust(90.5, true, 0),
+///     Err(GeoError::InvalidLatitude(90.5))
+/// );
+/// assert_eq!(
+///     decimal_to_dms_precise_rust(-180.5, false, 0),
+///     Err(GeoError::InvalidLongitude(-180.5))
+/// );
+/// ```
+pub fn decimal_to_dms_precise_rust(
+    decimal: f64,
+    is_latitude: bool,
+    seconds_decimals: usize,
+) -> Result<String, GeoError> {
+    require_finite(&[("decimal", decimal)])?;
+    if is_latitude && !(-90.0..=90.0).contains(&decimal) {
+        return Err(GeoError::InvalidLatitude(decimal));
+    }
+    if !is_latitude && !(-180.0..=180.0).contains(&decimal) {
+        return Err(GeoError::InvalidLongitude(decimal));
+    }
+
+    let hemisphere = match (is_latitude, decimal < 0.0) {
+        (true, false) => 'N',
+        (true, true) => 'S',
+        (false, false) => 'E',
+        (false, true) => 'W',
+    };
+
+    let decimals = seconds_decimals.min(DMS_MAX_SECONDS_DECIMALS);
+    let units_per_second = 10u64.pow(decimals as u32);
+    let total_units = (decimal.abs() * 3600.0 * units_per_second as f64).round() as u64;
+
+    let total_seconds = total_units / units_per_second;
+    let fraction = total_units % units_per_second;
+    let degrees = total_seconds / 3600;
+    let minutes = total_seconds % 3600 / 60;
+    let seconds = total_seconds % 60;
+
+    if decimals == 0 {
+        Ok(format!(
+            "{}°{:02}'{:02}\"{}",
+            degrees, minutes, seconds, hemisphere
+        ))
+    } else {
+        Ok(format!(
+            "{}°{:02}'{:02}.{:0width$}\"{}",
+            degrees,
+            minutes,
+            seconds,
+            fraction,
+            hemisphere,
+            width = decimals
+        ))
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn decimal_to_dms_precise(
+    decimal: f64,
+    is_latitude: bool,
+    seconds_decimals: usize,
+) -> Result<String, JsValue> {
+    decimal_to_dms_precise_rust(decimal, is_latitude, seconds_decimals)
+        .map_err(|err| JsValue::from_str(&err.to_string()))
+}
+
+/// Parses a coordinate written in any of the formats this crate understands.
+///
+/// The format is detected from the text:
+/// - A UTM coordinate, as a zone and band followed by easting and northing in meters, e.g.
+///   `18T 585628 4478677`, is converted with [`utm_to_lat_lon_rust`].
+/// - Any other text starting with a zone and band, e.g. `18TWL8395907523`, is read as an MGRS
+///   reference with [`mgrs_to_lat_lon_rust`].
+/// - Otherwise the text must be a latitude and longitude pair separated by a comma, or by
+///   whitespace when each half is a single number or ends in a hemisphere letter. Each half
+///   is parsed with [`dms_to_decimal_rust`], so decimal degrees and degrees, minutes and
+///   seconds are both accepted. Halves with hemisphere letters may come in either order.
+///
+/// # Returns
+/// - A `Result` containing `(latitude, longitude)` in decimal degrees, or
+///   `GeoError::InvalidInput` if the format is not recognised or ambiguous, or an error from
+///   the parser the text was dispatched to.
+///
+/// # Examples
+/// ```
+/// use rust::parse_coordinate_rust;
+///
+/// // Decimal degrees
+/// assert_eq!(parse_coordinate_rust("40.446, -79.982"), Ok((40.446, -79.982)));
+/// assert_eq!(parse_coordinate_rust("40.446 -79.982"), Ok((40.446, -79.982)));
+///
+/// // Degrees, minutes and seconds, in either order
+/// let (lat, lon) = parse_coordinate_rust("40°26'46\"N 79°58'56\"W").unwrap();
+/// assert!((lat - 40.446111).abs() < 1e-6 && (lon + 79.982222).abs() < 1e-6);
+/// let (lat, lon) = parse_coordinate_rust("W 79 58 56 N 40 26 46").unwrap();
+/// assert!((lat - 40.446111).abs() < 1e-6 && (lon + 79.982222).abs() < 1e-6);
+///
+/// // UTM
+/// let (lat, lon) = parse_coordinate_rust("32U 395201.31 5673135.24").unwrap();
+/// assert!((lat - 51.2).abs() < 1e-6 && (lon - 7.5).abs() < 1e-6);
+///
+/// // MGRS
+/// let (lat, lon) = parse_coordinate_rust("18TWL8395907523").unwrap();
+/// assert!((lat - 40.71435).abs() < 1e-4 && (lon + 74.00597).abs() < 1e-4);
+///
+/// // Error cases: unrecognisable, ambiguous and out of range input
+/// assert!(parse_coordinate_rust("somewhere").is_err());
+/// assert!(parse_coordinate_rust("40 26 46 79 58 56").is_err());
+/// assert!(parse_coordinate_rust("40N 41N").is_err());
+/// assert!(parse_coordinate_rust("95.0, 10.0").is_err());
+/// ```
+pub fn parse_coordinate_rust(input: &str) -> Result<(f64, f64), GeoError> {
+    let text = input.trim();
+    let unrecognised = || {
+        GeoError::InvalidInput(format!(
+            "'{}' is not a latitude/longitude pair, a UTM coordinate or an MGRS reference",
+            input
+        ))
+    };
+    if text.is_empty() {
+        return Err(unrecognised());
+    }
+
+    // Grid references start with a one or two digit zone directly followed by the band letter
+    let zone_digits = text.chars().take_while(|c| c.is_ascii_digit()).count();
+    let starts_with_zone = (1..=2).contains(&zone_digits)
+        && text[zone_digits..]
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic());
+    if starts_with_zone {
+        let tokens: Vec<&str> = text.split_whitespace().collect();
+        if let [zone_band, easting, northing] = tokens[..] {
+            if let (Ok(easting), Ok(northing)) = (easting.parse::<f64>(), northing.parse::<f64>()) {
+                if zone_band.len() == zone_digits + 1 {
+                    let zone = zone_band[..zone_digits]
+                        .parse()
+                        .map_err(|_| unrecognised())?;
+                    let band = zone_band.chars().last().unwrap_or_default();
+                    return utm_to_lat_lon_rust(zone, band, easting, northing);
+                }
+            }
+        }
+        // "40N 79W" also starts with digits and a letter, so fall back to the pair formats
+        if let Ok(position) = mgrs_to_lat_lon_rust(text) {
+            return Ok(position);
+        }
+    }
+
+    let (first, second) = split_coordinate_pair(text).ok_or_else(unrecognised)?;
+    let axis = |part: &str| {
+        part.chars()
+            .map(|c| c.to_ascii_uppercase())
+            .find(|c| "NSEW".contains(*c))
+            .map(|c| c == 'N' || c == 'S')
+    };
+    let (latitude_text, longitude_text) = match (axis(first), axis(second)) {
+        (Some(false), None) | (None, Some(true)) | (Some(false), Some(true)) => (second, first),
+        (Some(true), Some(true)) | (Some(false), Some(false)) => {
+            return Err(GeoError::InvalidInput(format!(
+                "'{}' has two {} but needs a latitude and a longitude",
+                input,
+                if axis(first) == Some(true) {
+                    "latitudes"
+                } else {
+                    "longitudes"
+                }
+            )))
+        }
+        _ => (first, second),
+    };
+
+    let latitude = dms_to_decimal_rust(latitude_text)?;
+    let longitude = dms_to_decimal_rust(longitude_text)?;
+    validate_lat_lon(latitude, longitude)?;
+    Ok((latitude, longitude))
+}
+
+/// Splits a latitude/longitude pair at a comma, at a hemisphere letter or at the single run of
+/// whitespace between two numbers. Returns `None` if there is no unambiguous split.
+fn split_coordinate_pair(text: &str) -> Option<(&str, &str)> {
+    if let Some((first, second)) = text.split_once(',') {
+        if second.contains(',') {
+            return None;
+        }
+        return Some((first.trim(), second.trim()));
+    }
+
+    let letters: Vec<usize> = text
+        .char_indices()
+        .filter(|(_, c)| "NSEWnsew".contains(*c))
+        .map(|(i, _)| i)
+        .collect();
+    match letters[..] {
+        // Leading hemisphere letters, as in "N 40 26 46 W 79 58 56"
+        [0, second] => Some((text[..second].trim(), text[second..].trim())),
+        // Trailing hemisphere letters, as in "40°26'46\"N 79°58'56\"W"
+        [first, _] => Some((text[..=first].trim(), text[first + 1..].trim())),
+        [] => match text.split_whitespace().collect::<Vec<_>>()[..] {
+            [first, second] => Some((first, second)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn parse_coordinate(input: &str) -> Result<Vec<f64>, JsValue> {
+    match parse_coordinate_rust(input) {
+        Ok((latitude, longitude)) => Ok(vec![latitude, longitude]),
+        Err(err) => Err(JsValue::from_str(&err.to_string())),
+    }
+}
+
+/// Converts a latitude and longitude in degrees to a unit vector from the Earth's center
+fn unit_vector(latitude: f64, longitude: f64) -> [f64; 3] {
+    let (sin_lat, cos_lat) = (latitude * PI / 180.0).sin_cos();
+    let (sin_lon, cos_lon) = (longitude * PI / 180.0).sin_cos();
+    [cos_lat * cos_lon, cos_lat * sin_lon, sin_lat]
+}
+
+/// Calculates the point at a given fraction of the great-circle path between two points.
+///
+/// The position is found by spherical linear interpolation (slerp) of the unit vectors of the
+/// two points, so equal steps in `fraction` give equal steps in distance along the path.
+///
+/// # Parameters
+/// - `lat1`, `lon1`: Latitude and longitude of the starting point in degrees.
+/// - `lat2`, `lon2`: Latitude and longitude of the end point in degrees.
+/// - `fraction`: Position along the path, from 0.0 (start) to 1.0 (end).
+///
+/// # Returns
+/// - A `(latitude, longitude)` tuple in degrees, or a `GeoError` if a coordinate is out of
+///   range, `fraction` is outside [0, 1] (it is not clamped), or the points are antipodal and
+///   the great circle between them is undefined. When the two points coincide that point is
+///   returned for any fraction.
 ///
 /// # Examples
 /// ```
-/// use rust::calculate_utm_zone;
+/// use rust::geodesic_interpolate_rust;
 ///
-/// // General case in the Northern Hemisphere
-/// let (zone, band) = calculate_utm_zone(40.0, -75.0).unwrap();
-/// assert_eq!(zone, 18);
-/// assert_eq!(band, 'T');
+/// // The end points are returned exactly
+/// assert_eq!(geodesic_interpolate_rust(10.0, 20.0, 30.0, 40.0, 0.0), Ok((10.0, 20.0)));
+/// assert_eq!(geodesic_interpolate_rust(10.0, 20.0, 30.0, 40.0, 1.0), Ok((30.0, 40.0)));
 ///
-/// // General case in the Southern Hemisphere
-/// let (zone, band) = calculate_utm_zone(-33.0, 151.0).unwrap();
-/// assert_eq!(zone, 56);
-/// assert_eq!(band, 'H');
+/// // A quarter of the way along the equator
+/// let (lat, lon) = geodesic_interpolate_rust(0.0, 0.0, 0.0, 80.0, 0.25).unwrap();
+/// assert!(lat.abs() < 1e-9);
+/// assert!((lon - 20.0).abs() < 1e-9);
 ///
-/// // Special case in Norway
-/// let (zone, band) = calculate_utm_zone(60.0, 5.0).unwrap();
-/// assert_eq!(zone, 32);
-/// assert_eq!(band, 'V');
+/// // Coincident points
+/// assert_eq!(geodesic_interpolate_rust(5.0, 5.0, 5.0, 5.0, 0.7), Ok((5.0, 5.0)));
 ///
-/// // Special case in Svalbard
-/// let (zone, band) = calculate_utm_zone(72.0, 7.0).unwrap();
-/// assert_eq!(zone, 31);
-/// assert_eq!(band, 'X');
+/// // End points are returned with their longitude normalized
+/// assert_eq!(geodesic_interpolate_rust(0.0, 350.0, 0.0, 10.0, 0.0), Ok((0.0, -10.0)));
 ///
-/// // Near the equator
-/// let (zone, band) = calculate_utm_zone(57.0, 1.0).unwrap();
-/// assert_eq!(zone, 31);
-/// assert_eq!(band, 'V');
+/// // Error cases: fraction outside [0, 1] and antipodal points
+/// assert!(geodesic_interpolate_rust(0.0, 0.0, 10.0, 10.0, 1.5).is_err());
+/// assert!(geodesic_interpolate_rust(0.0, 0.0, 0.0, 180.0, 0.5).is_err());
+/// ```
+pub fn geodesic_interpolate_rust(
+    lat1: f64,
+    lon1: f64,
+    lat2: f64,
+    lon2: f64,
+    fraction: f64,
+) -> Result<(f64, f64), GeoError> {
+    validate_lat_lon(lat1, lon1)?;
+    validate_lat_lon(lat2, lon2)?;
+    if !(0.0..=1.0).contains(&fraction) {
+        return Err(GeoError::InvalidInput(format!(
+            "fraction {} must be between 0 and 1",
+            fraction
+        )));
+    }
+
+    let lon1 = wrap_longitude(lon1);
+    let lon2 = wrap_longitude(lon2);
+
+    if fraction == 0.0 {
+        return Ok((lat1, lon1));
+    }
+    if fraction == 1.0 {
+        return Ok((lat2, lon2));
+    }
+
+    let v1 = unit_vector(lat1, lon1);
+    let v2 = unit_vector(lat2, lon2);
+
+    let cross = [
+        v1[1] * v2[2] - v1[2] * v2[1],
+        v1[2] * v2[0] - v1[0] * v2[2],
+        v1[0] * v2[1] - v1[1] * v2[0],
+    ];
+    let sin_omega = (cross[0].powi(2) + cross[1].powi(2) + cross[2].powi(2)).sqrt();
+    let cos_omega = v1[0] * v2[0] + v1[1] * v2[1] + v1[2] * v2[2];
+    let omega = sin_omega.atan2(cos_omega);
+
+    if omega < 1e-12 {
+        // Coincident points
+        return Ok((lat1, lon1));
+    }
+    if PI - omega < 1e-12 {
+        return Err(GeoError::InvalidInput(
+            "the great circle between antipodal points is undefined".to_string(),
+        ));
+    }
+
+    let weight1 = ((1.0 - fraction) * omega).sin() / sin_omega;
+    let weight2 = (fraction * omega).sin() / sin_omega;
+    let v: Vec<f64> = v1
+        .iter()
+        .zip(v2.iter())
+        .map(|(a, b)| weight1 * a + weight2 * b)
+        .collect();
+
+    let latitude = v[2].atan2(v[0].hypot(v[1])) * 180.0 / PI;
+    let longitude = v[1].atan2(v[0]) * 180.0 / PI;
+
+    Ok((latitude, longitude))
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn geodesic_interpolate(
+    lat1: f64,
+    lon1: f64,
+    lat2: f64,
+    lon2: f64,
+    fraction: f64,
+) -> Result<Vec<f64>, JsValue> {
+    match geodesic_interpolate_rust(lat1, lon1, lat2, lon2, fraction) {
+        Ok((latitude, longitude)) => Ok(vec![latitude, longitude]),
+        Err(err) => Err(JsValue::from_str(&err.to_string())),
+    }
+}
+
+/// Calculates the midpoint of the great-circle path between two points.
 ///
-/// // Another weird case between UK and Norway
-/// let (zone, band) = calculate_utm_zone(0.0, 33.0).unwrap();
-/// assert_eq!(zone, 36);
-/// assert_eq!(band, 'N');
+/// Equivalent to [`geodesic_interpolate_rust`] with a fraction of 0.5.
 ///
-/// // Edge case at the boundary of valid latitude for MGRS
-/// assert!(calculate_utm_zone(84.0, 15.0).is_err());
+/// # Examples
+/// ```
+/// use rust::geodesic_midpoint_rust;
 ///
-/// // Error case: Latitude out of range
-/// assert!(calculate_utm_zone(90.1, 0.0).is_err());
+/// // The midpoint of two equatorial points is the average longitude
+/// let (lat, lon) = geodesic_midpoint_rust(0.0, 10.0, 0.0, 30.0).unwrap();
+/// assert!(lat.abs() < 1e-9);
+/// assert!((lon - 20.0).abs() < 1e-9);
 ///
-/// // Error case: Longitude out of range
-/// assert!(calculate_utm_zone(0.0, 181.0).is_err());
+/// // Away from the equator the path bulges towards the pole
+/// let (lat, _) = geodesic_midpoint_rust(45.0, -30.0, 45.0, 30.0).unwrap();
+/// assert!(lat > 45.0);
 /// ```
-pub fn calculate_utm_zone(latitude: f64, longitude: f64) -> Result<(u32, char), UTMZoneError> {
-    if latitude < -90.0 || latitude > 90.0 {
-        return Err(UTMZoneError::InvalidLatitude(latitude));
+pub fn geodesic_midpoint_rust(
+    lat1: f64,
+    lon1: f64,
+    lat2: f64,
+    lon2: f64,
+) -> Result<(f64, f64), GeoError> {
+    geodesic_interpolate_rust(lat1, lon1, lat2, lon2, 0.5)
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn geodesic_midpoint(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> Result<Vec<f64>, JsValue> {
+    geodesic_interpolate(lat1, lon1, lat2, lon2, 0.5)
+}
+
+/// Returns the angular distance from the start of a path to a point and the difference between
+/// the bearing to that point and the bearing of the path, both in radians
+fn path_offset_angles(
+    latitude: f64,
+    longitude: f64,
+    path_start_lat: f64,
+    path_start_lon: f64,
+    path_end_lat: f64,
+    path_end_lon: f64,
+) -> Result<(f64, f64), GeoError> {
+    if haversine_distance_rust(path_start_lat, path_start_lon, path_end_lat, path_end_lon)? == 0.0 {
+        return Err(GeoError::InvalidInput(
+            "the path start and end points must differ".to_string(),
+        ));
+    }
+
+    let angular_distance =
+        haversine_distance_rust(path_start_lat, path_start_lon, latitude, longitude)?
+            / EARTH_MEAN_RADIUS;
+    let bearing_to_point =
+        initial_bearing_rust(path_start_lat, path_start_lon, latitude, longitude)?;
+    let bearing_of_path =
+        initial_bearing_rust(path_start_lat, path_start_lon, path_end_lat, path_end_lon)?;
+
+    Ok((
+        angular_distance,
+        (bearing_to_point - bearing_of_path) * PI / 180.0,
+    ))
+}
+
+/// Calculates the cross-track distance from a point to the great circle through two path
+/// points.
+///
+/// This is the shortest distance from the point to the great circle, signed so that points to
+/// the right of the direction of travel (from start to end) are positive and points to the left
+/// are negative. The great circle extends beyond the path end points.
+///
+/// # Parameters
+/// - `latitude`, `longitude`: The point to measure, in degrees.
+/// - `path_start_lat`, `path_start_lon`: Start of the path in degrees.
+/// - `path_end_lat`, `path_end_lon`: End of the path in degrees.
+///
+/// # Returns
+/// - The signed distance in meters, or a `GeoError` if any coordinate is out of range or the
+///   path start and end coincide.
+///
+/// # Examples
+/// ```
+/// use rust::cross_track_distance_rust;
+///
+/// // A point on an eastbound path along the equator
+/// let d = cross_track_distance_rust(0.0, 5.0, 0.0, 0.0, 0.0, 10.0).unwrap();
+/// assert!(d.abs() < 1e-6);
+///
+/// // 1 km north of the path is to the left of the direction of travel
+/// let offset = 1000.0 / 6371008.8 * 180.0 / std::f64::consts::PI;
+/// let d = cross_track_distance_rust(offset, 5.0, 0.0, 0.0, 0.0, 10.0).unwrap();
+/// assert!((d + 1000.0).abs() < 1e-6);
+///
+/// // 1 km south is to the right
+/// let d = cross_track_distance_rust(-offset, 5.0, 0.0, 0.0, 0.0, 10.0).unwrap();
+/// assert!((d - 1000.0).abs() < 1e-6);
+///
+/// // Error case: a path with no length
+/// assert!(cross_track_distance_rust(1.0, 1.0, 0.0, 0.0, 0.0, 0.0).is_err());
+/// ```
+pub fn cross_track_distance_rust(
+    latitude: f64,
+    longitude: f64,
+    path_start_lat: f64,
+    path_start_lon: f64,
+    path_end_lat: f64,
+    path_end_lon: f64,
+) -> Result<f64, GeoError> {
+    let (angular_distance, bearing_difference) = path_offset_angles(
+        latitude,
+        longitude,
+        path_start_lat,
+        path_start_lon,
+        path_end_lat,
+        path_end_lon,
+    )?;
+
+    Ok((angular_distance.sin() * bearing_difference.sin()).asin() * EARTH_MEAN_RADIUS)
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn cross_track_distance(
+    latitude: f64,
+    longitude: f64,
+    path_start_lat: f64,
+    path_start_lon: f64,
+    path_end_lat: f64,
+    path_end_lon: f64,
+) -> Result<f64, JsValue> {
+    cross_track_distance_rust(
+        latitude,
+        longitude,
+        path_start_lat,
+        path_start_lon,
+        path_end_lat,
+        path_end_lon,
+    )
+    .map_err(|err| JsValue::from_str(&err.to_string()))
+}
+
+/// Calculates the along-track distance from the start of a path to the point on its great
+/// circle closest to a given point.
+///
+/// The distance is measured along the great circle from the path start, and is negative when
+/// the closest point lies behind the start.
+///
+/// # Parameters
+/// Same as [`cross_track_distance_rust`].
+///
+/// # Returns
+/// - The signed distance in meters, or a `GeoError` if any coordinate is out of range or the
+///   path start and end coincide.
+///
+/// # Examples
+/// ```
+/// use rust::{along_track_distance_rust, haversine_distance_rust};
+///
+/// // Halfway along an eastbound path on the equator, 1 km off track
+/// let offset = 1000.0 / 6371008.8 * 180.0 / std::f64::consts::PI;
+/// let d = along_track_distance_rust(offset, 5.0, 0.0, 0.0, 0.0, 10.0).unwrap();
+/// let expected = haversine_distance_rust(0.0, 0.0, 0.0, 5.0).unwrap();
+/// assert!((d - expected).abs() < 1e-6);
+///
+/// // A point behind the start of the path
+/// let d = along_track_distance_rust(0.0, -1.0, 0.0, 0.0, 0.0, 10.0).unwrap();
+/// assert!(d < 0.0);
+/// ```
+pub fn along_track_distance_rust(
+    latitude: f64,
+    longitude: f64,
+    path_start_lat: f64,
+    path_start_lon: f64,
+    path_end_lat: f64,
+    path_end_lon: f64,
+) -> Result<f64, GeoError> {
+    let (angular_distance, bearing_difference) = path_offset_angles(
+        latitude,
+        longitude,
+        path_start_lat,
+        path_start_lon,
+        path_end_lat,
+        path_end_lon,
+    )?;
+
+    // Napier's rule on the right spherical triangle: tan(along) = tan(d13) * cos(angle)
+    let along = (angular_distance.sin() * bearing_difference.cos()).atan2(angular_distance.cos());
+
+    Ok(along * EARTH_MEAN_RADIUS)
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn along_track_distance(
+    latitude: f64,
+    longitude: f64,
+    path_start_lat: f64,
+    path_start_lon: f64,
+    path_end_lat: f64,
+    path_end_lon: f64,
+) -> Result<f64, JsValue> {
+    along_track_distance_rust(
+        latitude,
+        longitude,
+        path_start_lat,
+        path_start_lon,
+        path_end_lat,
+        path_end_lon,
+    )
+    .map_err(|err| JsValue::from_str(&err.to_string()))
+}
+
+/// Returns the signed spherical excess of a polygon in steradians, positive when the vertices
+/// run clockwise.
+///
+/// Each edge contributes the excess of the triangle it forms with the north pole, which keeps
+/// the sum exact on the sphere and handles edges that cross the antimeridian.
+fn signed_spherical_excess(points: &[(f64, f64)]) -> Result<f64, GeoError> {
+    for &(latitude, longitude) in points {
+        validate_lat_lon(latitude, longitude)?;
     }
-    if longitude < -180.0 || longitude > 180.0 {
-        return Err(UTMZoneError::InvalidLongitude(longitude));
+
+    // Drop repeated consecutive vertices, including the closing one, so an explicitly closed
+    // ring and an open one are treated alike
+    let mut ring: Vec<(f64, f64)> = Vec::with_capacity(points.len());
+    for &(latitude, longitude) in points {
+        let vertex = (latitude, wrap_longitude(longitude));
+        if ring.last() != Some(&vertex) {
+            ring.push(vertex);
+        }
+    }
+    while ring.len() > 1 && ring.first() == ring.last() {
+        ring.pop();
+    }
+    if ring.len() < 3 {
+        return Err(GeoError::InvalidInput(format!(
+            "a polygon needs at least 3 distinct vertices, got {}",
+            ring.len()
+        )));
     }
 
-    let zone_number = if latitude > 55.0 && latitude < 64.0 && longitude > 2.0 && longitude < 6.0 {
-        32
-    } else if latitude > 71.0 && longitude >= 6.0 && longitude < 9.0 {
-        31
-    } else if latitude > 71.0
-        && (longitude >= 9.0 && longitude < 12.0 || longitude >= 18.0 && longitude < 21.0)
-    {
-        33
-    } else if latitude > 71.0
-        && (longitude >= 21.0 && longitude < 24.0 || longitude >= 30.0 && longitude < 33.0)
-    {
-        35
-    } else {
-        ((longitude + 180.0) / 6.0).floor() as u32 % 60 + 1
-    };
+    let mut excess = 0.0;
+    for (i, &(lat1, lon1)) in ring.iter().enumerate() {
+        let (lat2, lon2) = ring[(i + 1) % ring.len()];
+        let delta_lambda = wrap_longitude(lon2 - lon1) * PI / 180.0;
+        let t1 = (lat1 * PI / 360.0).tan();
+        let t2 = (lat2 * PI / 360.0).tan();
+        excess += 2.0 * ((delta_lambda / 2.0).tan() * (t1 + t2)).atan2(1.0 + t1 * t2);
+    }
 
-    let latitude_band = get_mgrs_latitude_band(latitude)?;
+    Ok(excess)
+}
 
-    Ok((zone_number, latitude_band))
+/// Calculates the area enclosed by a polygon on a spherical Earth.
+///
+/// The area is the spherical excess of the polygon times the square of the mean Earth radius
+/// (6371008.8 m). This is within a few tenths of a percent of the WGS84 ellipsoidal area for
+/// polygons of moderate size. The polygon is closed automatically if the last vertex is not a
+/// repeat of the first, and edges follow great circles. Use [`polygon_is_clockwise_rust`] to
+/// find the orientation of the ring.
+///
+/// # Parameters
+/// - `points`: Vertices as `(latitude, longitude)` pairs in degrees.
+///
+/// # Returns
+/// - The absolute area in square meters, or a `GeoError` if a vertex is out of range or the
+///   polygon has fewer than 3 distinct vertices.
+///
+/// # Examples
+/// ```
+/// use rust::polygon_area_rust;
+///
+/// // A 1° x 1° box at the equator is about 12,364 km²
+/// let square = [(0.0, 0.0), (0.0, 1.0), (1.0, 1.0), (1.0, 0.0)];
+/// let area = polygon_area_rust(&square).unwrap();
+/// assert!((area - 1.2364e10).abs() / 1.2364e10 < 1e-3);
+///
+/// // Explicitly closing the ring or reversing it gives the same area
+/// let closed = [(0.0, 0.0), (0.0, 1.0), (1.0, 1.0), (1.0, 0.0), (0.0, 0.0)];
+/// assert!((polygon_area_rust(&closed).unwrap() - area).abs() < 1e-3);
+/// let reversed = [(1.0, 0.0), (1.0, 1.0), (0.0, 1.0), (0.0, 0.0)];
+/// assert!((polygon_area_rust(&reversed).unwrap() - area).abs() < 1e-3);
+///
+/// // A box straddling the antimeridian
+/// let straddling = [(0.0, 179.5), (0.0, -179.5), (1.0, -179.5), (1.0, 179.5)];
+/// assert!((polygon_area_rust(&straddling).unwrap() - area).abs() / area < 1e-9);
+///
+/// // Error cases: too few distinct vertices, latitude out of range
+/// assert!(polygon_area_rust(&[(0.0, 0.0), (1.0, 1.0), (0.0, 0.0)]).is_err());
+/// assert!(polygon_area_rust(&[(0.0, 0.0), (0.0, 1.0), (91.0, 1.0)]).is_err());
+/// ```
+pub fn polygon_area_rust(points: &[(f64, f64)]) -> Result<f64, GeoError> {
+    let excess = signed_spherical_excess(points)?;
+    Ok(excess.abs() * EARTH_MEAN_RADIUS * EARTH_MEAN_RADIUS)
+}
+
+/// Returns whether the vertices of a polygon run clockwise when seen from above the Earth's
+/// surface.
+///
+/// # Returns
+/// - `true` for a clockwise ring, or a `GeoError` under the same conditions as
+///   [`polygon_area_rust`].
+///
+/// # Examples
+/// ```
+/// use rust::polygon_is_clockwise_rust;
+///
+/// // East along the equator, then north: counterclockwise
+/// let square = [(0.0, 0.0), (0.0, 1.0), (1.0, 1.0), (1.0, 0.0)];
+/// assert_eq!(polygon_is_clockwise_rust(&square), Ok(false));
+///
+/// let reversed = [(1.0, 0.0), (1.0, 1.0), (0.0, 1.0), (0.0, 0.0)];
+/// assert_eq!(polygon_is_clockwise_rust(&reversed), Ok(true));
+/// ```
+pub fn polygon_is_clockwise_rust(points: &[(f64, f64)]) -> Result<bool, GeoError> {
+    Ok(signed_spherical_excess(points)? > 0.0)
+}
+
+/// Splits a flat `[lat0, lon0, lat1, lon1, ...]` array from JavaScript into vertices
+#[cfg(feature = "wasm")]
+fn flat_to_points(coordinates: &[f64]) -> Result<Vec<(f64, f64)>, GeoError> {
+    if !coordinates.len().is_multiple_of(2) {
+        return Err(GeoError::InvalidInput(
+            "coordinates must be latitude and longitude pairs".to_string(),
+        ));
+    }
+    Ok(coordinates
+        .chunks(2)
+        .map(|pair| (pair[0], pair[1]))
+        .collect())
 }
 
+/// Wasm entry point for [`polygon_area_rust`] taking the vertices as a flat
+/// `[lat0, lon0, lat1, lon1, ...]` array.
+#[cfg(feature = "wasm")]
 #[wasm_bindgen]
-pub fn get_utm_zone_from_lat_lon(latitude: f64, longitude: f64) -> Result<JsValue, JsValue> {
-    match calculate_utm_zone(latitude, longitude) {
-        Ok((zone_number, latitude_band)) => {
-            let result = format!("{}{}", zone_number, latitude_band);
-            Ok(JsValue::from_str(&result))
+pub fn polygon_area(coordinates: Vec<f64>) -> Result<f64, JsValue> {
+    flat_to_points(&coordinates)
+        .and_then(|points| polygon_area_rust(&points))
+        .map_err(|err| JsValue::from_str(&err.to_string()))
+}
+
+/// Wasm entry point for [`polygon_is_clockwise_rust`] taking the vertices as a flat
+/// `[lat0, lon0, lat1, lon1, ...]` array.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn polygon_is_clockwise(coordinates: Vec<f64>) -> Result<bool, JsValue> {
+    flat_to_points(&coordinates)
+        .and_then(|points| polygon_is_clockwise_rust(&points))
+        .map_err(|err| JsValue::from_str(&err.to_string()))
+}
+
+/// Re-exports of the native (non-wasm) API for glob imports.
+///
+/// ```
+/// use rust::prelude::*;
+///
+/// // Every re-export resolves from the glob import alone
+/// let _numeric = (
+///     calculate_twos_complement_rust,
+///     calculate_twos_complement_radix_rust,
+///     decimal_to_twos_complement_rust,
+///     decimal_to_twos_complement_grouped_rust,
+///     decimal_to_twos_complement_endian_rust,
+///     calculate_twos_complement_batch_rust::<&str>,
+///     decimal_to_twos_complement_batch_rust,
+///     decimal_to_radix_rust,
+///     sign_extend_rust,
+///     truncate_rust,
+///     twos_complement_add_rust,
+///     twos_complement_subtract_rust,
+///     twos_complement_and_rust,
+///     twos_complement_or_rust,
+///     twos_complement_xor_rust,
+///     twos_complement_not_rust,
+///     shift_left_rust,
+///     logical_shift_right_rust,
+///     arithmetic_shift_right_rust,
+///     decimal_to_excess_k_rust,
+///     excess_k_to_decimal_rust,
+///     decimal_to_excess_k_with_bias_rust,
+///     excess_k_to_decimal_with_bias_rust,
+///     calculate_ones_complement_rust,
+///     calculate_sign_magnitude_rust,
+///     decimal_to_ones_complement_rust,
+///     decimal_to_sign_magnitude_rust,
+///     bit_reverse_rust,
+///     rotate_left_rust,
+///     rotate_right_rust,
+///     decimal_to_twos_complement_saturating_rust,
+///     twos_complement_steps_rust,
+/// );
+/// let _encodings = (
+///     binary_to_gray_rust,
+///     gray_to_binary_rust,
+///     float_to_ieee754_rust,
+///     ieee754_to_float_rust,
+///     population_count_rust,
+///     parity_bit_rust,
+///     decimal_to_bcd_rust,
+///     bcd_to_decimal_rust,
+///     qformat_decode_rust,
+///     qformat_encode_rust,
+///     hamming_encode_rust,
+///     hamming_decode_rust,
+///     decimal_fraction_to_binary_rust,
+/// );
+/// let _geodetic = (
+///     lat_lon_to_xyz_rust,
+///     lat_lon_to_xyz_on,
+///     xyz_to_lat_lon_rust,
+///     xyz_to_lat_lon_on,
+///     ecef_to_enu_rust,
+///     enu_to_ecef_rust,
+///     normalize_longitude_rust,
+///     calculate_utm_zone,
+///     calculate_utm_zone_with_hemisphere,
+///     is_northern_hemisphere_rust,
+///     is_northern_band_rust,
+///     utm_zone_bounds_rust,
+///     lat_lon_to_utm_rust,
+///     utm_to_lat_lon_rust,
+///     utm_convergence_rust,
+///     lat_lon_to_mgrs_rust,
+///     mgrs_to_lat_lon_rust,
+///     mgrs_grid_square_rust,
+///     utm_point_scale_rust,
+///     lat_lon_to_utm_json_rust,
+///     utm_zones_for_bbox_rust,
+///     lat_lon_to_xyz_with_geoid_rust,
+///     calculate_utm_zone_with_options,
+///     lat_lon_to_utm_with_options_rust,
+///     lat_lon_to_web_mercator_rust,
+///     web_mercator_to_lat_lon_rust,
+/// );
+/// let _navigation = (
+///     haversine_distance_rust,
+///     vincenty_distance_rust,
+///     initial_bearing_rust,
+///     magnetic_to_true_bearing_rust,
+///     true_to_magnetic_bearing_rust,
+///     destination_point_rust,
+///     dms_to_decimal_rust,
+///     decimal_to_dms_rust,
+///     geodesic_interpolate_rust,
+///     geodesic_midpoint_rust,
+///     cross_track_distance_rust,
+///     along_track_distance_rust,
+///     polygon_area_rust,
+///     polygon_is_clockwise_rust,
+///     decimal_to_dms_precise_rust,
+///     parse_coordinate_rust,
+/// );
+///
+/// let _: Option<TwosComplementError> = None;
+/// let _: Option<TwosComplementErrorKind> = None;
+/// let _: Endianness = Endianness::Little;
+/// let _: RoundMode = RoundMode::Nearest;
+/// let _: Option<TwosComplementSteps> = None;
+/// let _: Option<GeoError> = None;
+/// let _: Option<UTMZoneError> = None;
+/// let _: Ellipsoid = Ellipsoid::WGS84;
+/// let _: UtmOptions = UtmOptions::default();
+///
+/// assert_eq!(decimal_to_twos_complement_rust(-5, 8), Ok("11111011".to_string()));
+/// assert_eq!(calculate_utm_zone(51.2, 7.5), Ok((32, 'U')));
+/// ```
+pub mod prelude {
+    // Two's complement and other number representations
+    pub use crate::{
+        arithmetic_shift_right_rust, bcd_to_decimal_rust, binary_to_gray_rust, bit_reverse_rust,
+        calculate_ones_complement_rust, calculate_sign_magnitude_rust,
+        calculate_twos_complement_batch_rust, calculate_twos_complement_radix_rust,
+        calculate_twos_complement_rust, decimal_fraction_to_binary_rust, decimal_to_bcd_rust,
+        decimal_to_excess_k_rust, decimal_to_excess_k_with_bias_rust,
+        decimal_to_ones_complement_rust, decimal_to_radix_rust, decimal_to_sign_magnitude_rust,
+        decimal_to_twos_complement_batch_rust, decimal_to_twos_complement_endian_rust,
+        decimal_to_twos_complement_grouped_rust, decimal_to_twos_complement_rust,
+        decimal_to_twos_complement_saturating_rust, excess_k_to_decimal_rust,
+        excess_k_to_decimal_with_bias_rust, float_to_ieee754_rust, gray_to_binary_rust,
+        hamming_decode_rust, hamming_encode_rust, ieee754_to_float_rust, logical_shift_right_rust,
+        parity_bit_rust, population_count_rust, qformat_decode_rust, qformat_encode_rust,
+        rotate_left_rust, rotate_right_rust, shift_left_rust, sign_extend_rust, truncate_rust,
+        twos_complement_add_rust, twos_complement_and_rust, twos_complement_not_rust,
+        twos_complement_or_rust, twos_complement_steps_rust, twos_complement_subtract_rust,
+        twos_complement_xor_rust, Endianness, RoundMode, TwosComplementError,
+        TwosComplementErrorKind, TwosComplementSteps,
+    };
+
+    // Geodetic coordinates, UTM/MGRS and great-circle navigation
+    pub use crate::{
+        along_track_distance_rust, calculate_utm_zone, calculate_utm_zone_with_hemisphere,
+        calculate_utm_zone_with_options, cross_track_distance_rust, decimal_to_dms_precise_rust,
+        decimal_to_dms_rust, destination_point_rust, dms_to_decimal_rust, ecef_to_enu_rust,
+        enu_to_ecef_rust, geodesic_interpolate_rust, geodesic_midpoint_rust,
+        haversine_distance_rust, initial_bearing_rust, is_northern_band_rust,
+        is_northern_hemisphere_rust, lat_lon_to_mgrs_rust, lat_lon_to_utm_json_rust,
+        lat_lon_to_utm_rust, lat_lon_to_utm_with_options_rust, lat_lon_to_web_mercator_rust,
+        lat_lon_to_xyz_on, lat_lon_to_xyz_rust, lat_lon_to_xyz_with_geoid_rust,
+        magnetic_to_true_bearing_rust, mgrs_grid_square_rust, mgrs_to_lat_lon_rust,
+        normalize_longitude_rust, parse_coordinate_rust, polygon_area_rust,
+        polygon_is_clockwise_rust, true_to_magnetic_bearing_rust, utm_convergence_rust,
+        utm_point_scale_rust, utm_to_lat_lon_rust, utm_zone_bounds_rust, utm_zones_for_bbox_rust,
+        vincenty_distance_rust, web_mercator_to_lat_lon_rust, xyz_to_lat_lon_on,
+        xyz_to_lat_lon_rust, Ellipsoid, GeoError, UTMZoneError, UtmOptions,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Small deterministic SplitMix64 generator so the property tests are reproducible
+    /// without pulling in a dependency.
+    struct SplitMix64(u64);
+
+    impl SplitMix64 {
+        fn next_u64(&mut self) -> u64 {
+            self.0 = self.0.wrapping_add(0x9E3779B97F4A7C15);
+            let mut z = self.0;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+            z ^ (z >> 31)
+        }
+
+        /// Uniform sample in the open interval (low, high)
+        fn uniform(&mut self, low: f64, high: f64) -> f64 {
+            let unit = ((self.next_u64() >> 11) as f64 + 0.5) / (1u64 << 53) as f64;
+            low + (high - low) * unit
+        }
+    }
+
+    #[cfg(feature = "wasm")]
+    #[test]
+    fn error_kinds_are_distinct_across_the_wasm_boundary() {
+        let overflow = decimal_to_twos_complement_error_kind(128, 8);
+        let invalid_input = calculate_twos_complement_error_kind("12");
+        let invalid_size = decimal_to_twos_complement_error_kind(1, 0);
+
+        assert_eq!(overflow, TwosComplementErrorKind::OverflowError);
+        assert_eq!(invalid_input, TwosComplementErrorKind::InvalidInput);
+        assert_eq!(invalid_size, TwosComplementErrorKind::InvalidSize);
+        assert_ne!(overflow as u32, invalid_input as u32);
+        assert_ne!(overflow as u32, invalid_size as u32);
+        assert_ne!(invalid_input as u32, invalid_size as u32);
+    }
+
+    #[test]
+    fn geo_functions_reject_non_finite_inputs() {
+        fn is_invalid_input<T>(result: Result<T, GeoError>) -> bool {
+            matches!(result, Err(GeoError::InvalidInput(_)))
+        }
+
+        for bad in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            assert!(is_invalid_input(lat_lon_to_xyz_rust(bad, 0.0, 0.0)));
+            assert!(is_invalid_input(lat_lon_to_xyz_rust(0.0, bad, 0.0)));
+            assert!(is_invalid_input(lat_lon_to_xyz_rust(0.0, 0.0, bad)));
+            assert!(is_invalid_input(lat_lon_to_xyz_on(
+                0.0,
+                0.0,
+                0.0,
+                &Ellipsoid {
+                    a: bad,
+                    inv_flattening: 298.0
+                }
+            )));
+            assert!(is_invalid_input(xyz_to_lat_lon_rust(bad, 0.0, 0.0)));
+            assert!(is_invalid_input(xyz_to_lat_lon_rust(6378137.0, bad, 0.0)));
+            assert!(is_invalid_input(xyz_to_lat_lon_rust(6378137.0, 0.0, bad)));
+            assert!(is_invalid_input(ecef_to_enu_rust(
+                bad, 0.0, 0.0, 0.0, 0.0, 0.0
+            )));
+            assert!(is_invalid_input(ecef_to_enu_rust(
+                6378137.0, 0.0, 0.0, bad, 0.0, 0.0
+            )));
+            assert!(is_invalid_input(enu_to_ecef_rust(
+                0.0, bad, 0.0, 0.0, 0.0, 0.0
+            )));
+            assert!(is_invalid_input(enu_to_ecef_rust(
+                0.0, 0.0, 0.0, 0.0, 0.0, bad
+            )));
+
+            assert!(is_invalid_input(calculate_utm_zone(bad, 0.0)));
+            assert!(is_invalid_input(calculate_utm_zone(0.0, bad)));
+            assert!(is_invalid_input(calculate_utm_zone_with_hemisphere(
+                bad, 0.0
+            )));
+            assert!(is_invalid_input(lat_lon_to_utm_rust(0.0, bad)));
+            assert!(is_invalid_input(utm_to_lat_lon_rust(32, 'U', bad, 0.0)));
+            assert!(is_invalid_input(utm_to_lat_lon_rust(
+                32, 'U', 500000.0, bad
+            )));
+            assert!(is_invalid_input(utm_convergence_rust(bad, 0.0)));
+            assert!(is_invalid_input(lat_lon_to_mgrs_rust(bad, 0.0, 5)));
+            assert!(is_invalid_input(mgrs_grid_square_rust(0.0, bad)));
+
+            assert!(is_invalid_input(haversine_distance_rust(
+                0.0, 0.0, bad, 0.0
+            )));
+            assert!(is_invalid_input(vincenty_distance_rust(0.0, bad, 0.0, 0.0)));
+            assert!(is_invalid_input(initial_bearing_rust(bad, 0.0, 0.0, 0.0)));
+            assert!(is_invalid_input(destination_point_rust(0.0, 0.0, bad, 1.0)));
+            assert!(is_invalid_input(destination_point_rust(0.0, 0.0, 0.0, bad)));
+            assert!(is_invalid_input(geodesic_interpolate_rust(
+                0.0, 0.0, 1.0, 1.0, bad
+            )));
+            assert!(is_invalid_input(geodesic_midpoint_rust(0.0, bad, 1.0, 1.0)));
+            assert!(is_invalid_input(cross_track_distance_rust(
+                bad, 0.0, 0.0, 0.0, 1.0, 1.0
+            )));
+            assert!(is_invalid_input(along_track_distance_rust(
+                0.0, 0.0, 0.0, 0.0, 1.0, bad
+            )));
+            assert!(is_invalid_input(normalize_longitude_rust(bad)));
+            assert!(is_invalid_input(magnetic_to_true_bearing_rust(bad, 0.0)));
+            assert!(is_invalid_input(magnetic_to_true_bearing_rust(0.0, bad)));
+            assert!(is_invalid_input(true_to_magnetic_bearing_rust(bad, 0.0)));
+            assert!(is_invalid_input(true_to_magnetic_bearing_rust(0.0, bad)));
+            assert!(is_invalid_input(lat_lon_to_web_mercator_rust(bad, 0.0)));
+            assert!(is_invalid_input(lat_lon_to_web_mercator_rust(0.0, bad)));
+            assert!(is_invalid_input(web_mercator_to_lat_lon_rust(bad, 0.0)));
+            assert!(is_invalid_input(web_mercator_to_lat_lon_rust(0.0, bad)));
+            assert!(is_invalid_input(polygon_area_rust(&[
+                (0.0, 0.0),
+                (0.0, 1.0),
+                (bad, 1.0)
+            ])));
+        }
+    }
+
+    #[test]
+    fn strict_utm_zones_ignore_the_norway_and_svalbard_exceptions() {
+        let strict = UtmOptions {
+            apply_special_zones: false,
+            ..UtmOptions::default()
+        };
+
+        assert_eq!(calculate_utm_zone(60.0, 5.0), Ok((32, 'V')));
+        assert_eq!(
+            calculate_utm_zone_with_options(60.0, 5.0, &strict),
+            Ok((31, 'V'))
+        );
+        for longitude in [7.0, 10.0, 20.0, 23.0, 31.0] {
+            let strict_zone = calculate_utm_zone_with_options(75.0, longitude, &strict)
+                .unwrap()
+                .0;
+            assert_eq!(strict_zone, ((longitude + 180.0) / 6.0).floor() as u32 + 1);
+        }
+
+        // Outside the special areas both modes agree
+        for longitude in (-179..180).step_by(7) {
+            let longitude = longitude as f64;
+            assert_eq!(
+                calculate_utm_zone_with_options(40.0, longitude, &strict),
+                calculate_utm_zone(40.0, longitude)
+            );
+        }
+    }
+
+    #[test]
+    fn lat_lon_xyz_round_trip() {
+        let mut rng = SplitMix64(0x5EED);
+
+        for _ in 0..10_000 {
+            let latitude = rng.uniform(-90.0, 90.0);
+            let longitude = rng.uniform(-180.0, 180.0);
+            let height = rng.uniform(-1000.0, 100_000.0);
+
+            let (x, y, z) = lat_lon_to_xyz_rust(latitude, longitude, height).unwrap();
+            let (lat, lon, h) = xyz_to_lat_lon_rust(x, y, z).unwrap();
+
+            assert!(
+                (lat - latitude).abs() < 1e-6,
+                "latitude {} came back as {}",
+                latitude,
+                lat
+            );
+            assert!(
+                (lon - longitude).abs() < 1e-6,
+                "longitude {} came back as {}",
+                longitude,
+                lon
+            );
+            assert!(
+                (h - height).abs() < 1e-3,
+                "height {} came back as {}",
+                height,
+                h
+            );
+        }
+    }
+
+    #[test]
+    fn lat_lon_xyz_round_trip_on_other_ellipsoids() {
+        let mut rng = SplitMix64(0xE11150);
+
+        for ellipsoid in [Ellipsoid::GRS80, Ellipsoid::CLARKE_1866] {
+            for _ in 0..10_000 {
+                let latitude = rng.uniform(-90.0, 90.0);
+                let longitude = rng.uniform(-180.0, 180.0);
+                let height = rng.uniform(-1000.0, 100_000.0);
+
+                let (x, y, z) = lat_lon_to_xyz_on(latitude, longitude, height, &ellipsoid).unwrap();
+                let (lat, lon, h) = xyz_to_lat_lon_on(x, y, z, &ellipsoid).unwrap();
+
+                assert!((lat - latitude).abs() < 1e-6);
+                assert!((lon - longitude).abs() < 1e-6);
+                assert!((h - height).abs() < 1e-3);
+            }
+        }
+    }
+
+    /// Checks the `libm` forwarding used by `no_std` builds against the `std` methods it
+    /// replaces. The test harness links `std`, so both are available here and the trait
+    /// methods have to be called explicitly.
+    #[cfg(not(feature = "std"))]
+    mod float_math {
+        use super::*;
+
+        fn assert_close(libm: f64, std: f64) {
+            if std.is_nan() {
+                assert!(libm.is_nan(), "libm gave {} where std gave NaN", libm);
+            } else {
+                assert!(
+                    libm == std || (libm - std).abs() <= 1e-12 * std.abs().max(1.0),
+                    "libm gave {} where std gave {}",
+                    libm,
+                    std
+                );
+            }
+        }
+
+        #[test]
+        fn libm_methods_match_std() {
+            for x in [-720.5, -7.25, -1.0, -0.3, 0.0, 0.4, 0.99, 2.5, 1e3] {
+                assert_close(FloatMath::sin(x), x.sin());
+                assert_close(FloatMath::cos(x), x.cos());
+                assert_close(FloatMath::tan(x), x.tan());
+                assert_close(FloatMath::sin_cos(x).0, x.sin_cos().0);
+                assert_close(FloatMath::sin_cos(x).1, x.sin_cos().1);
+                assert_close(FloatMath::asin(x), x.asin());
+                assert_close(FloatMath::atan(x), x.atan());
+                assert_close(FloatMath::atan2(x, -2.0), x.atan2(-2.0));
+                assert_close(FloatMath::sinh(x / 100.0), (x / 100.0).sinh());
+                assert_close(FloatMath::cosh(x / 100.0), (x / 100.0).cosh());
+                assert_close(FloatMath::asinh(x), x.asinh());
+                assert_close(FloatMath::atanh(x), x.atanh());
+                assert_close(FloatMath::sqrt(x), x.sqrt());
+                assert_close(FloatMath::hypot(x, 3.0), x.hypot(3.0));
+                for n in [-3, 0, 2, 5] {
+                    assert_close(FloatMath::powi(x, n), x.powi(n));
+                }
+                assert_close(FloatMath::floor(x), x.floor());
+                assert_close(FloatMath::ceil(x), x.ceil());
+                assert_close(FloatMath::round(x), x.round());
+                assert_close(FloatMath::trunc(x), x.trunc());
+                assert_close(FloatMath::rem_euclid(x, 360.0), x.rem_euclid(360.0));
+                assert_close(FloatMath::rem_euclid(x, -3.0), x.rem_euclid(-3.0));
+            }
+        }
+    }
+
+    /// Tests of the wasm entry points. `JsValue` only works on wasm, so these run under
+    /// `wasm-pack test --node`.
+    #[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+    mod wasm {
+        use super::*;
+        use wasm_bindgen_test::wasm_bindgen_test;
+
+        fn field(object: &JsValue, name: &str) -> JsValue {
+            js_sys::Reflect::get(object, &JsValue::from_str(name)).unwrap()
+        }
+
+        #[wasm_bindgen_test]
+        fn full_utm_result_has_every_field() {
+            let json = get_full_utm_from_lat_lon(51.2, 7.5).unwrap();
+            let utm = js_sys::JSON::parse(&json.as_string().unwrap()).unwrap();
+
+            assert_eq!(field(&utm, "zone").as_f64(), Some(32.0));
+            assert_eq!(field(&utm, "band").as_string(), Some("U".to_string()));
+            assert!((field(&utm, "easting").as_f64().unwrap() - 395201.31).abs() < 0.01);
+            assert!((field(&utm, "northing").as_f64().unwrap() - 5673135.24).abs() < 0.01);
+            assert_eq!(field(&utm, "hemisphere").as_string(), Some("N".to_string()));
+
+            let json = get_full_utm_from_lat_lon(-33.9, 18.4).unwrap();
+            let utm = js_sys::JSON::parse(&json.as_string().unwrap()).unwrap();
+            assert_eq!(field(&utm, "hemisphere").as_string(), Some("S".to_string()));
+        }
+
+        #[wasm_bindgen_test]
+        fn full_utm_errors_are_message_strings() {
+            let err = get_full_utm_from_lat_lon(91.0, 0.0).unwrap_err();
+            assert_eq!(
+                err.as_string(),
+                Some(GeoError::InvalidLatitude(91.0).to_string())
+            );
         }
-        Err(err) => Err(JsValue::from_str(&err.to_string())),
     }
 }
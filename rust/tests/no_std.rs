@@ -0,0 +1,48 @@
+//! Builds the crate without default features for a bare-metal target, so `cargo test` checks
+//! that the `no_std` core still compiles. Running cargo needs a host, so this is skipped on wasm.
+#![cfg(not(target_arch = "wasm32"))]
+
+use std::path::Path;
+use std::process::Command;
+
+const TARGET: &str = "thumbv7em-none-eabihf";
+
+#[test]
+fn core_builds_without_std_for_a_bare_metal_target() {
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let libdir = Command::new(rustc)
+        .args(["--print", "target-libdir", "--target", TARGET])
+        .output()
+        .expect("failed to run rustc");
+    let libdir = String::from_utf8_lossy(&libdir.stdout);
+    if !Path::new(libdir.trim()).exists() {
+        eprintln!(
+            "skipping: install the {} target with `rustup target add {}`",
+            TARGET, TARGET
+        );
+        return;
+    }
+
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let output = Command::new(cargo)
+        .args([
+            "build",
+            "--quiet",
+            "--no-default-features",
+            "--target",
+            TARGET,
+        ])
+        .arg("--manifest-path")
+        .arg(Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml"))
+        .arg("--target-dir")
+        .arg(Path::new(env!("CARGO_TARGET_TMPDIR")).join("no-std"))
+        .env("RUSTFLAGS", "-D warnings")
+        .output()
+        .expect("failed to run cargo");
+
+    assert!(
+        output.status.success(),
+        "the no_std build failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}